@@ -0,0 +1,297 @@
+use stm32f1xx_hal::backup_domain::BackupDomain;
+
+use crate::calendar::{Calendar, Weekday};
+
+/// How many alarms may be set.
+pub const ALARMS: usize = 4;
+
+/// First backup data register used for the alarms,
+/// every alarm occupies one register.
+pub const ALARMS_REGISTER: usize = 0;
+
+/// Backup data register with the days of the week skipped by the alarms
+/// that don't fit into the registers of the alarms, right after them.
+pub const ALARM_DAYS_REGISTER: usize = ALARMS_REGISTER + ALARMS;
+
+pub const SNOOZE_SECONDS: u32 = 9 * 60;
+
+const SECONDS_IN_DAY: u32 = 24 * 60 * 60;
+
+/// The RTC alarm interrupt may be handled late,
+/// the alarm still goes off within this many seconds.
+const DUE_SECONDS: u32 = 60;
+
+/// Days of the week of an alarm, a bit for each,
+/// from Monday in the lowest bit to Sunday.
+pub const EVERY_DAY: u8 = 0x7F;
+
+const ENABLED_BIT: u16 = 1 << 15;
+const MINUTES_MASK: u16 = 0x7FF;
+
+/// Skipped days are saved, so zeroed bits go off every day.
+/// Monday to Thursday are in the register of the alarm,
+/// the rest in [ALARM_DAYS_REGISTER](`ALARM_DAYS_REGISTER`).
+const SKIPPED_DAYS_SHIFT: u16 = 11;
+const REGISTER_DAYS_MASK: u8 = 0x0F;
+const REGISTER_DAYS: u8 = 4;
+const OTHER_DAYS: u8 = 7 - REGISTER_DAYS;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Alarm {
+    hours: u8,
+    minutes: u8,
+    enabled: bool,
+    days: u8,
+}
+
+impl Alarm {
+    /// Creates an alarm going off every day.
+    pub fn new(hours: u8, minutes: u8, enabled: bool) -> Self {
+        Self {
+            hours: hours.clamp(0, 23),
+            minutes: minutes.clamp(0, 59),
+            enabled,
+            days: EVERY_DAY,
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self::new(0, 0, false)
+    }
+
+    pub fn hours(&self) -> u8 {
+        self.hours
+    }
+
+    pub fn minutes(&self) -> u8 {
+        self.minutes
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Sets the hour of the alarm,
+    /// gets clamped to 0 - 23.
+    pub fn set_hours(&mut self, hours: u8) {
+        self.hours = hours.clamp(0, 23);
+    }
+
+    /// Sets the minute of the alarm,
+    /// gets clamped to 0 - 59.
+    pub fn set_minutes(&mut self, minutes: u8) {
+        self.minutes = minutes.clamp(0, 59);
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Days of the week the alarm goes off on, see [EVERY_DAY](`EVERY_DAY`).
+    pub fn days(&self) -> u8 {
+        self.days
+    }
+
+    /// Sets the days of the week the alarm goes off on,
+    /// an alarm without any day never goes off.
+    pub fn set_days(&mut self, days: u8) {
+        self.days = days & EVERY_DAY;
+    }
+
+    pub fn on_day(&self, weekday: Weekday) -> bool {
+        self.days & (1 << weekday as u8) != 0
+    }
+
+    pub fn set_on_day(&mut self, weekday: Weekday, on: bool) {
+        if on {
+            self.days |= 1 << weekday as u8;
+        } else {
+            self.days &= !(1 << weekday as u8);
+        }
+    }
+
+    /// Seconds from the given ticks to the next time the alarm goes off.
+    /// If the alarm time is right now, the next day is returned.
+    ///
+    /// Ticks are expected to start at midnight UTC,
+    /// as the ticks of a [Calendar](`crate::calendar::Calendar`) do,
+    /// the alarm is in local time, offset from UTC by given seconds.
+    pub fn seconds_until(&self, ticks: u32, utc_offset: i32) -> u32 {
+        let alarm = self.hours as u32 * 60 * 60 + self.minutes as u32 * 60;
+        let now = (ticks as i64 + utc_offset as i64).rem_euclid(SECONDS_IN_DAY as i64) as u32;
+
+        match (alarm + SECONDS_IN_DAY - now) % SECONDS_IN_DAY {
+            0 => SECONDS_IN_DAY,
+            seconds => seconds,
+        }
+    }
+
+    /// Ticks of the next time the alarm goes off after the given ticks,
+    /// on one of its days. None if the alarm has no days.
+    /// The alarm is in the local time of the calendar at the time it goes off,
+    /// daylight saving time might start or end before that.
+    pub fn next_ticks(&self, ticks: u32, calendar: &Calendar) -> Option<u32> {
+        if self.days == 0 {
+            return None;
+        }
+
+        // the alarm is on one of the next seven days
+        let mut alarm = ticks;
+        for _ in 0..7 {
+            alarm = self.next_time(alarm, calendar);
+            if self.on_day(calendar.weekday_at(alarm)) {
+                return Some(alarm);
+            }
+        }
+
+        None
+    }
+
+    /// Ticks of the next time of the alarm, on any day.
+    fn next_time(&self, ticks: u32, calendar: &Calendar) -> u32 {
+        let (utc_offset, transition) = calendar.offset_at(ticks);
+        let alarm = ticks.saturating_add(self.seconds_until(ticks, utc_offset));
+
+        match transition {
+            // the alarm is looked for again with the offset after the transition
+            Some(transition) if alarm >= transition => {
+                let (utc_offset, _) = calendar.offset_at(transition);
+                let ticks = transition - 1;
+                ticks.saturating_add(self.seconds_until(ticks, utc_offset))
+            }
+            _ => alarm,
+        }
+    }
+
+    /// Encodes the alarm to be saved in a backup data register,
+    /// along with the skipped days that don't fit into it.
+    /// Zeroed register, ie. after the backup domain was reset,
+    /// decodes as a disabled alarm.
+    fn encode(&self) -> (u16, u16) {
+        let minutes = self.hours as u16 * 60 + self.minutes as u16;
+        let skipped = !self.days & EVERY_DAY;
+        let data = minutes
+            | ((skipped & REGISTER_DAYS_MASK) as u16) << SKIPPED_DAYS_SHIFT
+            | if self.enabled { ENABLED_BIT } else { 0 };
+
+        (data, (skipped >> REGISTER_DAYS) as u16)
+    }
+
+    fn decode(data: u16, other_skipped: u16) -> Self {
+        let minutes = data & MINUTES_MASK;
+        if minutes >= 24 * 60 {
+            // corrupted data
+            return Self::disabled();
+        }
+
+        let mut alarm = Self::new(
+            (minutes / 60) as u8,
+            (minutes % 60) as u8,
+            data & ENABLED_BIT != 0,
+        );
+        let skipped = (data >> SKIPPED_DAYS_SHIFT) as u8 & REGISTER_DAYS_MASK
+            | (other_skipped as u8) << REGISTER_DAYS;
+        alarm.set_days(!skipped);
+        alarm
+    }
+}
+
+/// Keeps the alarms and their state,
+/// computes when the RTC alarm should be set to.
+pub struct Alarms {
+    alarms: [Alarm; ALARMS],
+    ringing: bool,
+    snoozed_till: Option<u32>,
+}
+
+impl Alarms {
+    pub fn new() -> Self {
+        Self {
+            alarms: [Alarm::disabled(); ALARMS],
+            ringing: false,
+            snoozed_till: None,
+        }
+    }
+
+    pub fn alarm(&self, index: usize) -> Alarm {
+        self.alarms[index]
+    }
+
+    pub fn set_alarm(&mut self, index: usize, alarm: Alarm) {
+        self.alarms[index] = alarm;
+    }
+
+    pub fn ringing(&self) -> bool {
+        self.ringing
+    }
+
+    /// Called when the RTC alarm fired.
+    pub fn ring(&mut self) {
+        self.ringing = true;
+        self.snoozed_till = None;
+    }
+
+    /// Stops ringing, to ring again after [SNOOZE_SECONDS](`SNOOZE_SECONDS`).
+    pub fn snooze(&mut self, ticks: u32) {
+        self.ringing = false;
+        self.snoozed_till = Some(ticks.saturating_add(SNOOZE_SECONDS));
+    }
+
+    /// Stops ringing until the next alarm.
+    pub fn dismiss(&mut self) {
+        self.ringing = false;
+        self.snoozed_till = None;
+    }
+
+    /// Ticks of the next time any of the alarms should go off,
+    /// given current ticks and the calendar the alarms are in local time of,
+    /// see [next_ticks](`Alarm::next_ticks`).
+    /// None if there is no enabled alarm and nothing is snoozed.
+    pub fn next_alarm_ticks(&self, ticks: u32, calendar: &Calendar) -> Option<u32> {
+        let next_alarm = self
+            .alarms
+            .iter()
+            .filter(|alarm| alarm.enabled())
+            .filter_map(|alarm| alarm.next_ticks(ticks, calendar))
+            .min();
+
+        match (next_alarm, self.snoozed_till) {
+            (Some(alarm), Some(snooze)) => Some(alarm.min(snooze)),
+            (alarm, snooze) => alarm.or(snooze),
+        }
+    }
+
+    /// Whether any of the alarms should go off at the ticks, or the snoozed one.
+    /// The RTC alarm might have been set before the time was changed.
+    pub fn due(&self, ticks: u32, calendar: &Calendar) -> bool {
+        self.next_alarm_ticks(ticks.saturating_sub(DUE_SECONDS), calendar)
+            .is_some_and(|alarm| alarm <= ticks)
+    }
+
+    pub fn load(&mut self, backup: &BackupDomain) {
+        let other_days = backup.read_data_register_low(ALARM_DAYS_REGISTER);
+        for (i, alarm) in self.alarms.iter_mut().enumerate() {
+            let other_skipped = (other_days >> (i as u8 * OTHER_DAYS)) & ((1 << OTHER_DAYS) - 1);
+            *alarm = Alarm::decode(
+                backup.read_data_register_low(ALARMS_REGISTER + i),
+                other_skipped,
+            );
+        }
+    }
+
+    pub fn save(&self, backup: &mut BackupDomain) {
+        let mut other_days = 0;
+        for (i, alarm) in self.alarms.iter().enumerate() {
+            let (data, other_skipped) = alarm.encode();
+            backup.write_data_register_low(ALARMS_REGISTER + i, data);
+            other_days |= other_skipped << (i as u8 * OTHER_DAYS);
+        }
+        backup.write_data_register_low(ALARM_DAYS_REGISTER, other_days);
+    }
+}
+
+impl Default for Alarms {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{
+    alarm::ALARMS,
+    button::ButtonState,
+    calendar::Weekday,
+    clock_app::AppState,
+    clock_display::{ClockDisplay, DisplayPart},
+    clock_display_viewer::DisplayView,
+    transition::TransitionKind,
+};
+
+use super::{ClockAppMode, ClockAppModes};
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum AlarmField {
+    Alarm,
+    Hours,
+    Minutes,
+    Enabled,
+    /// Whether the alarm goes off on the day, days after Monday.
+    Day(u8),
+}
+
+/// Edits the alarms kept in the clock state.
+/// The alarms are saved to the backup domain
+/// when leaving this mode.
+///
+/// First digit shows number of the alarm,
+/// main display its time and second side display
+/// whether it's on. When editing the days of the alarm,
+/// first side display shows the day and second side display
+/// whether the alarm goes off on it.
+pub struct AlarmEditAppMode {
+    alarm: usize,
+    field: AlarmField,
+}
+
+impl AlarmEditAppMode {
+    pub fn new() -> Self {
+        Self {
+            alarm: 0,
+            field: AlarmField::Alarm,
+        }
+    }
+
+    fn change(&mut self, app: AppState, increment: bool) {
+        let alarms = app.state.mut_alarms();
+        let mut alarm = alarms.alarm(self.alarm);
+
+        match self.field {
+            AlarmField::Alarm => {
+                self.alarm = Self::wrap(self.alarm, ALARMS, increment);
+                return;
+            }
+            AlarmField::Hours => {
+                alarm.set_hours(Self::wrap(alarm.hours() as usize, 24, increment) as u8)
+            }
+            AlarmField::Minutes => {
+                alarm.set_minutes(Self::wrap(alarm.minutes() as usize, 60, increment) as u8)
+            }
+            AlarmField::Enabled => alarm.set_enabled(!alarm.enabled()),
+            AlarmField::Day(day) => {
+                let weekday = Weekday::from_days_after_monday(day);
+                alarm.set_on_day(weekday, !alarm.on_day(weekday));
+            }
+        }
+
+        alarms.set_alarm(self.alarm, alarm);
+    }
+
+    fn wrap(value: usize, count: usize, increment: bool) -> usize {
+        if increment {
+            (value + 1) % count
+        } else {
+            (value + count - 1) % count
+        }
+    }
+}
+
+impl Default for AlarmEditAppMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClockAppMode for AlarmEditAppMode {
+    fn update(&mut self, app: AppState) {
+        let alarm = app.state.alarms().alarm(self.alarm);
+        let blink_off = app.state.calendar().seconds() % 2 == 1;
+        let hour_format = app.display.hour_format();
+
+        let display = app.display.clock_display();
+        let main_offset = ClockDisplay::get_part_offset(DisplayPart::MainDisplay);
+        let side_offset = ClockDisplay::get_part_offset(DisplayPart::SideDisplay1);
+
+        // the number of the alarm is on the first digit,
+        // the dot of the second one may mark PM of the hours
+        display.hide(DisplayPart::SideDisplay1);
+        display
+            .show_hours_at(main_offset, alarm.hours(), hour_format)
+            .unwrap();
+        display
+            .show_number_at(main_offset + 2, 2, alarm.minutes() as u32, true)
+            .unwrap();
+        match self.field {
+            AlarmField::Day(day) => display
+                .show_text(
+                    DisplayPart::SideDisplay1,
+                    Weekday::from_days_after_monday(day).abbreviation(),
+                )
+                .unwrap(),
+            _ => display
+                .show_number_at(side_offset, 1, self.alarm as u32 + 1, false)
+                .unwrap(),
+        }
+        let on = match self.field {
+            AlarmField::Day(day) => alarm.on_day(Weekday::from_days_after_monday(day)),
+            _ => alarm.enabled(),
+        };
+        display
+            .show_text(DisplayPart::SideDisplay2, if on { "on" } else { "oF" })
+            .unwrap();
+
+        if blink_off {
+            match self.field {
+                AlarmField::Alarm => display.hide_at(side_offset, 1),
+                AlarmField::Hours => display.hide_at(main_offset, 2),
+                AlarmField::Minutes => display.hide_at(main_offset + 2, 2),
+                AlarmField::Enabled | AlarmField::Day(_) => display.hide(DisplayPart::SideDisplay2),
+            }
+        }
+
+        display.set_colon(true);
+    }
+
+    fn handle_button(&mut self, app: AppState, button: usize, state: ButtonState) {
+        match (button, state) {
+            (0, ButtonState::JustPressed) => {
+                self.field = match self.field {
+                    AlarmField::Alarm => AlarmField::Hours,
+                    AlarmField::Hours => AlarmField::Minutes,
+                    AlarmField::Minutes => AlarmField::Enabled,
+                    AlarmField::Enabled => AlarmField::Day(0),
+                    AlarmField::Day(day) if day < 6 => AlarmField::Day(day + 1),
+                    AlarmField::Day(_) => AlarmField::Alarm,
+                };
+            }
+            (1, ButtonState::JustPressed | ButtonState::LongPress) => self.change(app, true),
+            (2, ButtonState::JustPressed | ButtonState::LongPress) => self.change(app, false),
+            (3, ButtonState::JustPressed) => *app.current_mode = ClockAppModes::NormalMode,
+            _ => (),
+        }
+    }
+
+    fn run(&mut self, app: AppState) {
+        self.alarm = 0;
+        self.field = AlarmField::Alarm;
+        app.display.hide_all();
+    }
+
+    fn stop(&mut self, app: AppState) {
+        app.state.alarms().save(app.backup);
+        app.display
+            .transition_to_view(DisplayView::ClockView, TransitionKind::SlideRight);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{
+    button::ButtonState,
+    clock_app::AppState,
+    clock_display::{ClockDisplay, DisplayPart},
+    clock_display_viewer::DisplayView,
+    transition::TransitionKind,
+};
+
+use super::{ClockAppMode, ClockAppModes};
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum CountdownField {
+    Hours,
+    Minutes,
+    Seconds,
+}
+
+/// Sets, starts and pauses the countdown timer kept in the clock state.
+/// The countdown keeps running even when this mode is left,
+/// its expiry is signalled by [ClockApp](`crate::clock_app::ClockApp`).
+///
+/// Main display shows minutes and seconds,
+/// first side display shows hours.
+pub struct CountdownAppMode {
+    field: CountdownField,
+}
+
+impl CountdownAppMode {
+    pub fn new() -> Self {
+        Self {
+            field: CountdownField::Hours,
+        }
+    }
+
+    fn field_seconds(field: CountdownField) -> u32 {
+        match field {
+            CountdownField::Hours => 60 * 60,
+            CountdownField::Minutes => 60,
+            CountdownField::Seconds => 1,
+        }
+    }
+}
+
+impl Default for CountdownAppMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClockAppMode for CountdownAppMode {
+    fn update(&mut self, app: AppState) {
+        let countdown = app.state.countdown();
+        let remaining = countdown.remaining();
+        let blink_off = !countdown.running() && app.state.calendar().seconds() % 2 == 1;
+
+        let display = app.display.clock_display();
+        let main_offset = ClockDisplay::get_part_offset(DisplayPart::MainDisplay);
+
+        display
+            .show_number(DisplayPart::SideDisplay1, remaining / 60 / 60, true)
+            .unwrap();
+        display
+            .show_number_at(main_offset, 2, remaining / 60 % 60, true)
+            .unwrap();
+        display
+            .show_number_at(main_offset + 2, 2, remaining % 60, true)
+            .unwrap();
+        display.hide(DisplayPart::SideDisplay2);
+
+        if blink_off {
+            match self.field {
+                CountdownField::Hours => display.hide(DisplayPart::SideDisplay1),
+                CountdownField::Minutes => display.hide_at(main_offset, 2),
+                CountdownField::Seconds => display.hide_at(main_offset + 2, 2),
+            }
+        }
+
+        display.set_colon(true);
+    }
+
+    fn handle_button(&mut self, app: AppState, button: usize, state: ButtonState) {
+        let countdown = app.state.mut_countdown();
+
+        match (button, state) {
+            (0, ButtonState::JustPressed) if countdown.running() => {
+                *app.current_mode = ClockAppModes::NormalMode;
+            }
+            (0, ButtonState::JustPressed) => {
+                self.field = match self.field {
+                    CountdownField::Hours => CountdownField::Minutes,
+                    CountdownField::Minutes => CountdownField::Seconds,
+                    CountdownField::Seconds => {
+                        *app.current_mode = ClockAppModes::NormalMode;
+                        CountdownField::Hours
+                    }
+                };
+            }
+            (1, ButtonState::JustPressed | ButtonState::LongPress) if !countdown.running() => {
+                let seconds = Self::field_seconds(self.field);
+                countdown.set_duration(countdown.remaining() + seconds);
+            }
+            (2, ButtonState::JustPressed | ButtonState::LongPress) if !countdown.running() => {
+                let seconds = Self::field_seconds(self.field);
+                countdown.set_duration(countdown.remaining().saturating_sub(seconds));
+            }
+            (3, ButtonState::JustPressed) => countdown.toggle(),
+            _ => (),
+        }
+    }
+
+    fn run(&mut self, app: AppState) {
+        self.field = CountdownField::Hours;
+        app.display.hide_all();
+    }
+
+    fn stop(&mut self, app: AppState) {
+        app.display
+            .transition_to_view(DisplayView::ClockView, TransitionKind::SlideRight);
+    }
+}
@@ -0,0 +1,98 @@
+use alloc::format;
+
+use crate::{
+    button::ButtonState,
+    clock_app::AppState,
+    clock_display::DisplayPart,
+    clock_display_viewer::DisplayView,
+    marquee::{Marquee, DEFAULT_STEP_MILLIS},
+    transition::TransitionKind,
+};
+
+use super::{ClockAppMode, ClockAppModes};
+
+/// Manual change of the brightness lasts this long,
+/// in seconds, then the automatic brightness is used again.
+const MANUAL_BRIGHTNESS_SECONDS: u32 = 30 * 60;
+
+/// Change of the brightness by a press, in percent.
+const BRIGHTNESS_STEP: i8 = 10;
+
+/// Shows the current time, in one of the views,
+/// and adjusts the brightness, automatically
+/// or by the buttons. Other modes are entered
+/// by pressing or holding the buttons.
+pub struct DefaultAppMode {
+    view: DisplayView,
+}
+
+impl DefaultAppMode {
+    pub fn new() -> Self {
+        Self {
+            view: DisplayView::ClockView,
+        }
+    }
+
+    fn next_view(&mut self, app: AppState) {
+        let next = (self.view as usize + 1) % core::mem::variant_count::<DisplayView>();
+        self.view = DisplayView::try_from(next).unwrap();
+        app.display
+            .transition_to_view(self.view, TransitionKind::SlideLeft);
+
+        // the days are counted to the date scrolled by first
+        if self.view == DisplayView::DaysUntilView {
+            if let Some(target) = app.state.target_date() {
+                let text = format!(
+                    "{:04}-{:02}-{:02}",
+                    target.year(),
+                    target.month(),
+                    target.day()
+                );
+                app.display.show_marquee(Marquee::new(
+                    &text,
+                    DisplayPart::Whole,
+                    DEFAULT_STEP_MILLIS,
+                ));
+            }
+        }
+    }
+
+    fn change_brightness(app: AppState, change: i8) {
+        let brightness = app.brightness.brightness() as i8;
+        app.brightness
+            .set_brightness(brightness.saturating_add(change));
+        app.brightness
+            .turn_off_for(app.state, MANUAL_BRIGHTNESS_SECONDS);
+    }
+}
+
+impl Default for DefaultAppMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClockAppMode for DefaultAppMode {
+    fn update(&mut self, app: AppState) {
+        app.brightness.update(app.state);
+    }
+
+    fn handle_button(&mut self, app: AppState, button: usize, state: ButtonState) {
+        match (button, state) {
+            (0, ButtonState::JustPressed) => self.next_view(app),
+            (0, ButtonState::LongPress) => *app.current_mode = ClockAppModes::StopwatchMode,
+            (1, ButtonState::JustPressed) => *app.current_mode = ClockAppModes::EditMode,
+            (2, ButtonState::JustPressed) => Self::change_brightness(app, -BRIGHTNESS_STEP),
+            (2, ButtonState::LongPress) => *app.current_mode = ClockAppModes::CountdownMode,
+            (3, ButtonState::JustPressed) => Self::change_brightness(app, BRIGHTNESS_STEP),
+            (3, ButtonState::LongPress) => *app.current_mode = ClockAppModes::AlarmEditMode,
+            _ => (),
+        }
+    }
+
+    /// The view is kept from the last time
+    /// this mode was shown.
+    fn run(&mut self, app: AppState) {
+        app.display.set_current_view(self.view);
+    }
+}
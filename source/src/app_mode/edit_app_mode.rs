@@ -0,0 +1,262 @@
+use crate::{
+    button::ButtonState,
+    calendar::Calendar,
+    clock_app::AppState,
+    clock_display::{ClockDisplay, DisplayPart, HourFormat},
+    clock_display_viewer::DisplayView,
+    rtc_time::RtcTime,
+    transition::TransitionKind,
+};
+
+use super::{ClockAppMode, ClockAppModes};
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum EditField {
+    Hours,
+    Minutes,
+    Seconds,
+    Year,
+    Month,
+    Day,
+    HourFormat,
+}
+
+/// Edits the date and time of the clock.
+///
+/// The calendar keeps running while editing,
+/// the edits are kept as offsets of each field
+/// and applied to the calendar upon saving,
+/// so no time is lost no matter how long the editing takes.
+/// Only the seconds, once changed, are kept as they were set,
+/// and the next second starts right when saving.
+///
+/// The time is shown as in the clock seconds view,
+/// the date as in the date view. The hour format
+/// is changed and saved right away, as in the settings mode.
+///
+/// Holding the first button discards the edits
+/// and switches to the settings mode.
+pub struct EditAppMode {
+    field: EditField,
+    hours: i8,
+    minutes: i8,
+    seconds: Option<u8>,
+    years: i32,
+    months: i8,
+    days: i8,
+}
+
+impl EditAppMode {
+    pub fn new() -> Self {
+        Self {
+            field: EditField::Hours,
+            hours: 0,
+            minutes: 0,
+            seconds: None,
+            years: 0,
+            months: 0,
+            days: 0,
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// The running calendar with the edits applied.
+    /// The ticks of the returned calendar are not updated.
+    fn edited(&self, calendar: &Calendar) -> Calendar {
+        let mut edited = calendar.clone();
+
+        let year = (calendar.year() as i32 + self.years).clamp(1, u16::MAX as i32);
+        edited.set_year(year as u16);
+        edited.set_month(Self::offset(calendar.month() - 1, self.months, 12) + 1);
+
+        let days = Calendar::days_in_month(edited.month(), Calendar::is_leap_year(edited.year()))
+            .unwrap_or(31);
+        edited.set_day(Self::offset(edited.day() - 1, self.days, days) + 1);
+
+        edited.set_hours(Self::offset(calendar.hours(), self.hours, 24));
+        edited.set_minutes(Self::offset(calendar.minutes(), self.minutes, 60));
+        edited.set_seconds(self.seconds.unwrap_or(calendar.seconds()));
+
+        edited
+    }
+
+    fn offset(value: u8, offset: i8, count: u8) -> u8 {
+        (value as i16 + offset as i16).rem_euclid(count as i16) as u8
+    }
+
+    fn change(&mut self, app: AppState, increment: bool) {
+        let step = if increment { 1 } else { -1 };
+
+        match self.field {
+            EditField::Hours => self.hours = (self.hours + step) % 24,
+            EditField::Minutes => self.minutes = (self.minutes + step) % 60,
+            EditField::Seconds => {
+                let seconds = self
+                    .seconds
+                    .unwrap_or_else(|| app.state.calendar().seconds());
+                self.seconds = Some(Self::offset(seconds, step, 60));
+            }
+            EditField::Year => self.years = self.years.saturating_add(step as i32),
+            EditField::Month => self.months = (self.months + step) % 12,
+            EditField::Day => self.days = (self.days + step) % 31,
+            EditField::HourFormat => {
+                let hour_format = app.display.hour_format().toggled();
+                app.display.set_hour_format(hour_format);
+                hour_format.save(app.backup);
+            }
+        }
+    }
+
+    /// Whether any field of the date or time was changed,
+    /// the hour format is saved right when it's changed.
+    fn is_edited(&self) -> bool {
+        self.hours != 0
+            || self.minutes != 0
+            || self.seconds.is_some()
+            || self.years != 0
+            || self.months != 0
+            || self.days != 0
+    }
+
+    /// Applies the edits to the calendar and writes it to the RTC.
+    /// The calibration is told whether the time was corrected,
+    /// if the seconds were set, to measure the drift of the RTC.
+    /// Nothing is written if nothing was edited.
+    fn save(&self, app: AppState) {
+        if !self.is_edited() {
+            return;
+        }
+
+        let calendar = app.state.mut_calendar();
+
+        // the calendar is a second behind while the seconds interrupt
+        // is pending, the edits are applied to the time of the RTC
+        let now = RtcTime::now(app.rtc);
+        calendar.add_seconds(now.ticks() as i64 - calendar.estimated_ticks() as i64);
+        let old_ticks = calendar.to_ticks();
+
+        *calendar = self.edited(calendar);
+        calendar.sync_ticks();
+        let ticks = calendar.to_ticks();
+
+        app.rtc.set_time(ticks);
+
+        let calibration = app.state.mut_calibration();
+        if self.seconds.is_some() {
+            calibration.time_set(old_ticks, ticks);
+            // writing the prescaler reloads it, the next second starts now
+            calibration.apply(app.rtc, app.backup);
+        } else {
+            calibration.time_shifted(ticks as i64 - old_ticks as i64);
+        }
+        calibration.save(app.backup);
+
+        // the pending seconds interrupt still counts the second, for the countdown,
+        // the calendar that gets a second ahead of the RTC by it
+        // is synchronized with the RTC on the next update of the state
+    }
+}
+
+impl Default for EditAppMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClockAppMode for EditAppMode {
+    fn update(&mut self, app: AppState) {
+        let edited = self.edited(app.state.calendar());
+        let blink_off = app.state.calendar().seconds() % 2 == 1;
+        let hour_format = app.display.hour_format();
+
+        let display = app.display.clock_display();
+        let main_offset = ClockDisplay::get_part_offset(DisplayPart::MainDisplay);
+
+        match self.field {
+            EditField::Hours | EditField::Minutes | EditField::Seconds => {
+                display.hide(DisplayPart::SideDisplay1);
+                display
+                    .show_hours_at(main_offset, edited.hours(), hour_format)
+                    .unwrap();
+                display
+                    .show_number_at(main_offset + 2, 2, edited.minutes() as u32, true)
+                    .unwrap();
+                display
+                    .show_number(DisplayPart::SideDisplay2, edited.seconds() as u32, true)
+                    .unwrap();
+                display.set_colon(true);
+            }
+            EditField::Year | EditField::Month | EditField::Day => {
+                display
+                    .show_ordinal(DisplayPart::SideDisplay1, edited.day() as u32, true)
+                    .unwrap();
+                display
+                    .show_number(DisplayPart::MainDisplay, edited.year() as u32, true)
+                    .unwrap();
+                display
+                    .show_ordinal(DisplayPart::SideDisplay2, edited.month() as u32, true)
+                    .unwrap();
+                display.set_colon(false);
+            }
+            EditField::HourFormat => {
+                let hours = match hour_format {
+                    HourFormat::TwentyFourHour => 24,
+                    HourFormat::TwelveHour => 12,
+                };
+                display.hide(DisplayPart::Whole);
+                display.show_text(DisplayPart::SideDisplay1, "hF").unwrap();
+                display
+                    .show_number(DisplayPart::MainDisplay, hours, false)
+                    .unwrap();
+                display.set_colon(false);
+            }
+        }
+
+        if blink_off {
+            match self.field {
+                EditField::Hours => display.hide_at(main_offset, 2),
+                EditField::Minutes => display.hide_at(main_offset + 2, 2),
+                EditField::Seconds | EditField::Month => display.hide(DisplayPart::SideDisplay2),
+                EditField::Year | EditField::HourFormat => display.hide(DisplayPart::MainDisplay),
+                EditField::Day => display.hide(DisplayPart::SideDisplay1),
+            }
+        }
+    }
+
+    fn handle_button(&mut self, app: AppState, button: usize, state: ButtonState) {
+        match (button, state) {
+            (0, ButtonState::LongPress) => *app.current_mode = ClockAppModes::SettingsMode,
+            (0, ButtonState::JustPressed) => {
+                self.field = match self.field {
+                    EditField::Hours => EditField::Minutes,
+                    EditField::Minutes => EditField::Seconds,
+                    EditField::Seconds => EditField::Year,
+                    EditField::Year => EditField::Month,
+                    EditField::Month => EditField::Day,
+                    EditField::Day => EditField::HourFormat,
+                    EditField::HourFormat => EditField::Hours,
+                };
+            }
+            (1, ButtonState::JustPressed | ButtonState::LongPress) => self.change(app, true),
+            (2, ButtonState::JustPressed | ButtonState::LongPress) => self.change(app, false),
+            (3, ButtonState::JustPressed) => {
+                *app.current_mode = ClockAppModes::NormalMode;
+                self.save(app);
+            }
+            _ => (),
+        }
+    }
+
+    fn run(&mut self, app: AppState) {
+        self.reset();
+        app.display.hide_all();
+    }
+
+    fn stop(&mut self, app: AppState) {
+        app.display
+            .transition_to_view(DisplayView::ClockView, TransitionKind::SlideRight);
+    }
+}
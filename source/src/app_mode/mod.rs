@@ -0,0 +1,32 @@
+use crate::{button::ButtonState, clock_app::AppState};
+
+pub mod alarm_edit_app_mode;
+pub mod countdown_app_mode;
+pub mod default_app_mode;
+pub mod edit_app_mode;
+pub mod settings_app_mode;
+pub mod stopwatch_app_mode;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum ClockAppModes {
+    NormalMode = 0,
+    EditMode = 1,
+    StopwatchMode = 2,
+    CountdownMode = 3,
+    AlarmEditMode = 4,
+    SettingsMode = 5,
+}
+
+pub trait ClockAppMode {
+    /// Called from the display timer interrupt,
+    /// only for the mode that is currently active.
+    fn update(&mut self, app: AppState);
+
+    fn handle_button(&mut self, app: AppState, button: usize, state: ButtonState);
+
+    /// Called upon switching to this mode.
+    fn run(&mut self, _app: AppState) {}
+
+    /// Called upon switching from this mode to another one.
+    fn stop(&mut self, _app: AppState) {}
+}
@@ -0,0 +1,134 @@
+use crate::{
+    button::ButtonState,
+    clock_app::AppState,
+    clock_display::{DisplayPart, HourFormat},
+    clock_display_viewer::DisplayView,
+    number_format::NumberFormat,
+    rtc_calibration::Calibration,
+    transition::TransitionKind,
+};
+
+use super::{ClockAppMode, ClockAppModes};
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum SettingsField {
+    Calibration,
+    HourFormat,
+}
+
+/// Edits the settings of the clock, the correction
+/// of the RTC drift and the hour format.
+/// The correction is applied to the RTC and saved
+/// to the backup domain when leaving this mode,
+/// the hour format is saved right when it's changed.
+///
+/// First side display shows the setting,
+/// main display its value.
+pub struct SettingsAppMode {
+    field: SettingsField,
+    applied: Calibration,
+}
+
+impl SettingsAppMode {
+    pub fn new() -> Self {
+        Self {
+            field: SettingsField::Calibration,
+            applied: Calibration::default(),
+        }
+    }
+
+    fn change(&mut self, app: AppState, increment: bool) {
+        match self.field {
+            SettingsField::Calibration => {
+                let calibration = app.state.mut_calibration();
+                let step = if increment { 1 } else { -1 };
+                calibration.set_ppm(calibration.ppm().saturating_add(step));
+            }
+            SettingsField::HourFormat => {
+                let hour_format = app.display.hour_format().toggled();
+                app.display.set_hour_format(hour_format);
+                hour_format.save(app.backup);
+            }
+        }
+    }
+}
+
+impl Default for SettingsAppMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClockAppMode for SettingsAppMode {
+    fn update(&mut self, app: AppState) {
+        let blink_off = app.state.calendar().seconds() % 2 == 1;
+        let ppm = app.state.calibration().ppm();
+        let hour_format = app.display.hour_format();
+
+        let display = app.display.clock_display();
+
+        display.hide(DisplayPart::MainDisplay);
+        display.hide(DisplayPart::SideDisplay2);
+        match self.field {
+            SettingsField::Calibration => {
+                display.show_text(DisplayPart::SideDisplay1, "CA").unwrap();
+                display.show_formatted(DisplayPart::MainDisplay, ppm as i64, NumberFormat::new());
+            }
+            SettingsField::HourFormat => {
+                display.show_text(DisplayPart::SideDisplay1, "hF").unwrap();
+                let hours = match hour_format {
+                    HourFormat::TwentyFourHour => 24,
+                    HourFormat::TwelveHour => 12,
+                };
+                display
+                    .show_number(DisplayPart::MainDisplay, hours, false)
+                    .unwrap();
+            }
+        }
+
+        if blink_off {
+            display.hide(DisplayPart::MainDisplay);
+        }
+
+        display.set_colon(false);
+    }
+
+    fn handle_button(&mut self, app: AppState, button: usize, state: ButtonState) {
+        match (button, state) {
+            (0, ButtonState::JustPressed) => {
+                self.field = match self.field {
+                    SettingsField::Calibration => SettingsField::HourFormat,
+                    SettingsField::HourFormat => SettingsField::Calibration,
+                };
+            }
+            (1, ButtonState::JustPressed | ButtonState::LongPress) => self.change(app, true),
+            (2, ButtonState::JustPressed | ButtonState::LongPress) => self.change(app, false),
+            (3, ButtonState::JustPressed) => *app.current_mode = ClockAppModes::NormalMode,
+            _ => (),
+        }
+    }
+
+    fn run(&mut self, app: AppState) {
+        self.field = SettingsField::Calibration;
+        self.applied = *app.state.calibration();
+        app.display.hide_all();
+    }
+
+    fn stop(&mut self, app: AppState) {
+        let calibration = *app.state.calibration();
+
+        if calibration != self.applied {
+            // the prescaler is written only if it changes,
+            // writing it restarts the current second
+            if calibration.reload_changed(&self.applied) {
+                calibration.apply(app.rtc, app.backup);
+            } else {
+                calibration.apply_calibration_register(app.backup);
+            }
+            calibration.save(app.backup);
+        }
+
+        app.display
+            .transition_to_view(DisplayView::ClockView, TransitionKind::SlideRight);
+    }
+}
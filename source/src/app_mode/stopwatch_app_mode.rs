@@ -0,0 +1,111 @@
+use crate::{
+    button::ButtonState,
+    clock_app::AppState,
+    clock_display::{ClockDisplay, DisplayPart},
+    clock_display_viewer::DisplayView,
+    transition::TransitionKind,
+};
+
+use super::{ClockAppMode, ClockAppModes};
+
+/// Shows the stopwatch kept in the clock state.
+/// The stopwatch keeps running even when
+/// this mode is left.
+///
+/// Main display shows minutes and seconds,
+/// second side display shows hundredths and
+/// first side display shows hours, or index of the lap
+/// if a lap is shown.
+pub struct StopwatchAppMode {
+    shown_lap: Option<usize>,
+}
+
+impl StopwatchAppMode {
+    pub fn new() -> Self {
+        Self { shown_lap: None }
+    }
+
+    fn show_next_lap(&mut self, laps_count: usize) {
+        self.shown_lap = match self.shown_lap {
+            None if laps_count > 0 => Some(0),
+            Some(lap) if lap + 1 < laps_count => Some(lap + 1),
+            _ => None,
+        };
+    }
+}
+
+impl Default for StopwatchAppMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClockAppMode for StopwatchAppMode {
+    fn update(&mut self, app: AppState) {
+        let stopwatch = app.state.stopwatch();
+        let elapsed = stopwatch.elapsed(app.state.timer().now());
+        let shown_lap = self
+            .shown_lap
+            .and_then(|lap| stopwatch.lap_time(lap).map(|time| (lap, time)));
+
+        let (hundredths, side) = match shown_lap {
+            Some((lap, time)) => (time, lap as u32 + 1),
+            None => (elapsed, elapsed / 100 / 60 / 60),
+        };
+
+        let seconds = hundredths / 100;
+        let display = app.display.clock_display();
+        let main_offset = ClockDisplay::get_part_offset(DisplayPart::MainDisplay);
+
+        display
+            .show_number_at(main_offset, 2, seconds / 60 % 60, true)
+            .unwrap();
+        display
+            .show_number_at(main_offset + 2, 2, seconds % 60, true)
+            .unwrap();
+        display
+            .show_number(DisplayPart::SideDisplay2, hundredths % 100, true)
+            .unwrap();
+
+        if side > 0 {
+            // hours over 99 are not interesting, show the lower digits only
+            display
+                .show_number(DisplayPart::SideDisplay1, side % 100, false)
+                .unwrap();
+        } else {
+            display.hide(DisplayPart::SideDisplay1);
+        }
+
+        display.set_colon(true);
+    }
+
+    fn handle_button(&mut self, app: AppState, button: usize, state: ButtonState) {
+        if state != ButtonState::JustPressed {
+            return;
+        }
+
+        let now = app.state.timer().now();
+        let stopwatch = app.state.mut_stopwatch();
+        match button {
+            0 => stopwatch.toggle(now),
+            1 if stopwatch.running() => stopwatch.lap(now),
+            1 => {
+                stopwatch.reset();
+                self.shown_lap = None;
+            }
+            2 => self.show_next_lap(stopwatch.laps_count()),
+            3 => *app.current_mode = ClockAppModes::NormalMode,
+            _ => (),
+        }
+    }
+
+    fn run(&mut self, app: AppState) {
+        self.shown_lap = None;
+        app.display.hide_all();
+    }
+
+    fn stop(&mut self, app: AppState) {
+        app.display
+            .transition_to_view(DisplayView::ClockView, TransitionKind::SlideRight);
+    }
+}
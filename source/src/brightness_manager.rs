@@ -1,15 +1,30 @@
-use alloc::vec;
+use alloc::{vec, vec::Vec};
 
 use crate::{
+    calendar::Calendar,
     clock_display_viewer::ClockDisplayViewer,
     clock_state::ClockState,
     linear_interpolation::{LinearInterpolation, Point},
+    sun::Location,
 };
 
+const NIGHT_BRIGHTNESS: u16 = 1;
+
+/// Brightness of the digits at the lowest brightness,
+/// the digits are not visible when dimmer.
+pub const LOWEST_DISPLAY_BRIGHTNESS: u16 = 0xFFFF - 12000;
+
+/// Brightness is lowered to the night brightness
+/// until this time, even if the twilight ends later.
+const BEDTIME: u16 = 23 * 60;
+
 pub struct BrightnessManager {
     yellow_interpolation: LinearInterpolation<u16, u16>,
     blue_interpolation: LinearInterpolation<u16, u16>,
     brightness_interpolation: LinearInterpolation<u16, u16>,
+    location: Option<Location>,
+    /// Day and UTC offset the brightness curve was made for.
+    curve_day: Option<(u8, i32)>,
     current_brightness: u8,
     display_brightness: [u16; 8],
     off_till: Option<u32>,
@@ -27,33 +42,33 @@ impl BrightnessManager {
                 Point::new(100, 0xFFFF - 2500),
             ]),
             blue_interpolation: LinearInterpolation::new(vec![
-                Point::new(0, 0xFFFF - 12000),
+                Point::new(0, LOWEST_DISPLAY_BRIGHTNESS),
                 Point::new(1, 0xFFFF - 11990),
                 Point::new(10, 0xFFFF - 11700),
                 Point::new(20, 0xFFFF - 11300),
                 Point::new(50, 0xFFFF - 10000),
                 Point::new(100, 0xFFFF - 3000),
             ]),
-            brightness_interpolation: LinearInterpolation::new(vec![
-                Point::new(0, 1),
-                Point::new(5 * 60, 1),
-                Point::new(6 * 60, 20),
-                Point::new(7 * 60, 50),
-                Point::new(8 * 60, 70),
-                Point::new(12 * 60, 100),
-                Point::new(18 * 60, 90),
-                Point::new(20 * 60, 70),
-                Point::new(21 * 60, 30),
-                Point::new(22 * 60, 20),
-                Point::new(23 * 60, 1),
-                Point::new(24 * 60, 1),
-            ]),
+            brightness_interpolation: LinearInterpolation::new(Self::fixed_curve()),
+            location: None,
+            curve_day: None,
             current_brightness: 100,
             display_brightness: [0xFFFF; 8],
             off_till: None,
         }
     }
 
+    /// Sets the location of the clock, the brightness curve
+    /// is then made from the times of sunrise and sunset there.
+    pub fn set_location(&mut self, location: Option<Location>) {
+        self.location = location;
+        self.curve_day = None;
+    }
+
+    pub fn location(&self) -> Option<Location> {
+        self.location
+    }
+
     pub fn set_brightness(&mut self, brightness: i8) {
         self.current_brightness = brightness.clamp(1, 100) as u8;
 
@@ -93,6 +108,8 @@ impl BrightnessManager {
 
     pub fn update(&mut self, state: &ClockState) {
         let calendar = state.calendar();
+        self.update_curve(calendar);
+
         let minutes_in_day = calendar.hours() as u16 * 60u16 + calendar.minutes() as u16;
 
         let set_brightness = if let Some(off_till) = self.off_till {
@@ -116,6 +133,86 @@ impl BrightnessManager {
             }
         }
     }
+
+    /// Makes new brightness curve if the day,
+    /// or the UTC offset, has changed since the last one.
+    fn update_curve(&mut self, calendar: &Calendar) {
+        let day = Some((calendar.day(), calendar.utc_offset()));
+        if self.curve_day == day {
+            return;
+        }
+        self.curve_day = day;
+
+        // the heap is small, free the old curve first
+        self.brightness_interpolation = LinearInterpolation::new(Vec::new());
+        let curve = self
+            .location
+            .and_then(|location| Self::solar_curve(location, calendar))
+            .unwrap_or_else(Self::fixed_curve);
+        self.brightness_interpolation = LinearInterpolation::new(curve);
+    }
+
+    /// Brightness curve anchored on the civil twilight, sunrise and sunset.
+    /// The brightness rises from the dawn till the noon
+    /// and falls from the noon till the dusk, then keeps falling till the bedtime.
+    /// None if the sun doesn't rise or set on the day.
+    fn solar_curve(location: Location, calendar: &Calendar) -> Option<Vec<Point<u16, u16>>> {
+        let sun = location.sun_times(calendar);
+        let (sunrise, noon, sunset) = (sun.sunrise()?, sun.noon(), sun.sunset()?);
+        if !(sunrise < noon && noon < sunset) {
+            // the day goes over the midnight, too far from the time zone
+            return None;
+        }
+
+        // in summer far from the equator, the twilight lasts all night
+        let dawn = sun.dawn().filter(|&dawn| dawn < sunrise);
+        let dusk = sun.dusk().filter(|&dusk| dusk > sunset);
+
+        let anchors = [
+            Some((0, NIGHT_BRIGHTNESS)),
+            dawn.map(|dawn| (dawn, NIGHT_BRIGHTNESS)),
+            Some((sunrise, 30)),
+            Some((sunrise + 60, 70)).filter(|&(morning, _)| morning < noon),
+            Some((noon, 100)),
+            Some((sunset.saturating_sub(60), 90)).filter(|&(afternoon, _)| afternoon > noon),
+            Some((sunset, 70)),
+            dusk.map(|dusk| (dusk, 30)),
+            Some((BEDTIME, NIGHT_BRIGHTNESS)),
+            Some((24 * 60, NIGHT_BRIGHTNESS)),
+        ];
+
+        // points have to be in order, the ones that would
+        // go back, ie. late dusk after the bedtime, are left out
+        let mut points: Vec<Point<u16, u16>> = Vec::with_capacity(anchors.len());
+        let mut last_position = None;
+        for (position, brightness) in anchors.into_iter().flatten() {
+            if last_position.is_none_or(|last| position > last) {
+                points.push(Point::new(position, brightness));
+                last_position = Some(position);
+            }
+        }
+
+        Some(points)
+    }
+
+    /// Brightness curve used when the location is not known,
+    /// or the sun doesn't rise or set.
+    fn fixed_curve() -> Vec<Point<u16, u16>> {
+        vec![
+            Point::new(0, 1),
+            Point::new(5 * 60, 1),
+            Point::new(6 * 60, 20),
+            Point::new(7 * 60, 50),
+            Point::new(8 * 60, 70),
+            Point::new(12 * 60, 100),
+            Point::new(18 * 60, 90),
+            Point::new(20 * 60, 70),
+            Point::new(21 * 60, 30),
+            Point::new(22 * 60, 20),
+            Point::new(23 * 60, 1),
+            Point::new(24 * 60, 1),
+        ]
+    }
 }
 
 impl Default for BrightnessManager {
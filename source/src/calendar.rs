@@ -1,10 +1,99 @@
-use core::cmp::max;
+use core::{
+    cmp::{max, Ordering},
+    fmt,
+};
+
+use crate::time_zone::{DstRule, TimeZone};
+
+const SECONDS_IN_DAY: u32 = 24 * 60 * 60;
+
+/// Days from 1. 3. 0000 to 1. 1. 1970,
+/// see [days_from_civil](`Calendar::days_from_civil`).
+const UNIX_EPOCH_DAYS: u32 = 719468;
+
+/// Days from 1. 3. 0000 to 31. 12. 65535, the last day
+/// the year fits into u16, later dates are saturated to it.
+const LAST_DAYS: u32 = Calendar::days_from_civil(31, 12, u16::MAX);
+
+#[derive(Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Weekday {
+    Monday = 0,
+    Tuesday = 1,
+    Wednesday = 2,
+    Thursday = 3,
+    Friday = 4,
+    Saturday = 5,
+    Sunday = 6,
+}
+
+impl Weekday {
+    /// Weekday that is given number of days after Monday.
+    pub fn from_days_after_monday(days: u8) -> Self {
+        match days % 7 {
+            0 => Weekday::Monday,
+            1 => Weekday::Tuesday,
+            2 => Weekday::Wednesday,
+            3 => Weekday::Thursday,
+            4 => Weekday::Friday,
+            5 => Weekday::Saturday,
+            _ => Weekday::Sunday,
+        }
+    }
 
-#[derive(Clone, PartialEq, Eq)]
+    /// Two letter abbreviation, made out of letters
+    /// that may be shown on a seven segment display.
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Weekday::Monday => "Mo",
+            Weekday::Tuesday => "tu",
+            Weekday::Wednesday => "We",
+            Weekday::Thursday => "th",
+            Weekday::Friday => "Fr",
+            Weekday::Saturday => "Sa",
+            Weekday::Sunday => "Su",
+        }
+    }
+}
+
+/// Why a text could not be parsed as a date.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, defmt::Format)]
+pub enum ParseError {
+    /// The text is not in the `YYYY-MM-DDTHH:MM:SS` format,
+    /// optionally followed by `Z` or an offset `+HH:MM`.
+    InvalidFormat,
+    /// Some of the fields are out of their range, or the date
+    /// cannot be represented by ticks from the base year.
+    OutOfRange,
+}
+
+/// Why a date cannot be represented by a calendar.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, defmt::Format)]
+pub enum CalendarError {
+    /// Hours over 23, minutes or seconds over 59.
+    InvalidTime,
+    /// Day 0, or a day after the end of the month.
+    InvalidDay,
+    /// Month outside of 1 - 12.
+    InvalidMonth,
+    /// Year 0, or a year before the base year.
+    InvalidYear,
+    /// The date is too far after the base year
+    /// to be represented by u32 ticks.
+    TicksOverflow,
+}
+
+/// Date and time in local time.
+///
+/// Calendars are equal and ordered by the instant they represent,
+/// no matter their base year or time zone.
+#[derive(Clone)]
 pub struct Calendar {
     base_year: u16,
     ticks: u32,
     frozen: bool,
+    time_zone: TimeZone,
+    utc_offset: i32,
+    next_transition: Option<u32>,
     hours: u8,
     minutes: u8,
     seconds: u8,
@@ -14,11 +103,29 @@ pub struct Calendar {
 }
 
 impl Calendar {
+    /// Creates calendar with the year as its base year.
+    /// Invalid values get clamped, see [with_base_year](`Calendar::with_base_year`).
     pub fn new(hours: u8, minutes: u8, seconds: u8, day: u8, month: u8, year: u16) -> Self {
         Self::with_base_year(year, hours, minutes, seconds, day, month, year)
     }
 
+    /// Like [new](`Calendar::new`), but fails on invalid values.
+    pub fn try_new(
+        hours: u8,
+        minutes: u8,
+        seconds: u8,
+        day: u8,
+        month: u8,
+        year: u16,
+    ) -> Result<Self, CalendarError> {
+        Self::try_with_base_year(year, hours, minutes, seconds, day, month, year)
+    }
+
     /// Calendar may have a base year, that will be used when calling [to_ticks](`Calendar::to_ticks`).
+    ///
+    /// Invalid values get clamped the same way the setters clamp them,
+    /// year 0 is not supported, the lowest year, and base year, is 1.
+    /// Use [try_with_base_year](`Calendar::try_with_base_year`) to get an error instead.
     pub fn with_base_year(
         base_year: u16,
         hours: u8,
@@ -28,123 +135,249 @@ impl Calendar {
         month: u8,
         year: u16,
     ) -> Self {
+        let base_year = max(base_year, 1);
+        let year = max(year, base_year);
+        let month = month.clamp(1, 12);
+
         let mut result = Self {
             base_year,
-            hours,
-            minutes,
-            seconds,
-            day,
+            hours: hours.min(23),
+            minutes: minutes.min(59),
+            seconds: seconds.min(59),
+            day: day.clamp(1, Self::month_days(month, year)),
             month,
             year,
             frozen: false,
             ticks: 0,
+            time_zone: TimeZone::UTC,
+            utc_offset: 0,
+            next_transition: None,
         };
 
         result.ticks = result.to_ticks();
         result
     }
 
+    /// Like [with_base_year](`Calendar::with_base_year`),
+    /// but fails if any of the values is invalid,
+    /// or the ticks would overflow.
+    pub fn try_with_base_year(
+        base_year: u16,
+        hours: u8,
+        minutes: u8,
+        seconds: u8,
+        day: u8,
+        month: u8,
+        year: u16,
+    ) -> Result<Self, CalendarError> {
+        Self::validate(base_year, hours, minutes, seconds, day, month, year)?;
+        Ok(Self::with_base_year(
+            base_year, hours, minutes, seconds, day, month, year,
+        ))
+    }
+
     /// Calculate current date based off of the seconds elapsed since
-    /// a base date
+    /// a base date. Works for any base year and all u32 ticks,
+    /// dates after the year 65535 are saturated to 31. 12. 65535 23:59:59.
+    /// Base year 0 is taken as 1.
     pub fn from_ticks(base_year: u16, seconds: u32) -> Self {
-        let total_seconds = seconds;
-        let seconds = total_seconds % 60;
-
-        let total_minutes = total_seconds / 60;
-        let minutes = total_minutes % 60;
-
-        let total_hours = total_minutes / 60;
-        let hours = total_hours % 24;
-
-        let elapsed_days = total_hours / 24;
-        // elapsed_days / 365 / 4 subtracts leap days each 4 years, except for the last leap day
-        // that could have been in the last 3 years, solution for that day is presented
-        // below.
-        let estimated_years_elapsed = (elapsed_days - elapsed_days / 365 / 4) / 365;
-        let estimated_year = base_year as u32 + estimated_years_elapsed;
-
-        // estimated_years_elapsed may not be correct, imagine a situation,
-        // the current year is a leap year, ie. 2024, base is 2021 (1. 1. 00:00:00),
-        // it's 31. 12. 2024, the problem is that the last leap year, 2024, is not captured
-        // by elapsed_days / 365 / 4 in this case.
-        // If we did not subtract a day from the elapsed days, the program would
-        // incorrectly conclude it's 1. 1. 2025.
-        let mut elapsed_days_without_leap_day_in_past_3_years = total_hours / 24;
-        // uhh... naming of this property is 1. long, 2. still not correct,
-        // the variable should have leap days that were in last 3 years,
-        // but not in the current one.
-        for current_year in max(base_year as u32, estimated_year - 3)..=estimated_year - 1 {
-            elapsed_days_without_leap_day_in_past_3_years -=
-                if Self::is_leap_year(current_year as u16) {
-                    1
-                } else {
-                    0
-                };
+        let base_year = max(base_year, 1);
+        let days = Self::days_from_civil(1, 1, base_year) + seconds / SECONDS_IN_DAY;
+        Self::from_days(base_year, days, seconds % SECONDS_IN_DAY, seconds)
+    }
+
+    /// Calculate date from the seconds elapsed since 1. 1. 1970 00:00:00 UTC.
+    /// Every u32 timestamp is supported, up to 7. 2. 2106 06:28:15.
+    pub fn from_unix_timestamp(base_year: u16, timestamp: u32) -> Self {
+        let base_year = max(base_year, 1);
+        let days = timestamp / SECONDS_IN_DAY + UNIX_EPOCH_DAYS;
+
+        // the ticks are only an estimate, they are
+        // not expected to be correct if before the base year
+        let ticks = days
+            .saturating_sub(Self::days_from_civil(1, 1, base_year))
+            .saturating_mul(SECONDS_IN_DAY)
+            .saturating_add(timestamp % SECONDS_IN_DAY);
+
+        Self::from_days(base_year, days, timestamp % SECONDS_IN_DAY, ticks)
+    }
+
+    /// Parses ISO 8601 date and time, `YYYY-MM-DDTHH:MM:SS`,
+    /// optionally followed by `Z` or an offset from UTC, `+HH:MM` or `-HH:MM`.
+    ///
+    /// Date without an offset is in UTC. Date with an offset
+    /// gets a time zone with that fixed offset, so the local time
+    /// stays as parsed, use [set_time_zone](`Calendar::set_time_zone`)
+    /// to move it to another time zone.
+    /// Base year 0 is taken as 1, year 0 is out of range.
+    pub fn parse_iso8601(base_year: u16, text: &str) -> Result<Self, ParseError> {
+        let base_year = max(base_year, 1);
+        let text = text.as_bytes();
+        if text.len() < 19 {
+            return Err(ParseError::InvalidFormat);
         }
 
-        // this should be the correct year
-        let years_elapsed = (elapsed_days_without_leap_day_in_past_3_years
-            - elapsed_days_without_leap_day_in_past_3_years / 365 / 4)
-            / 365;
-        let year = base_year as u32 + years_elapsed;
-
-        if year != estimated_year && Self::is_leap_year(year as u16) {
-            // we went back one year, the current year is a leap year,
-            // because leap day is included in Self::days_in_month,
-            // it should not be present in the elapsed days...
-            elapsed_days_without_leap_day_in_past_3_years += 1;
+        let (date, offset) = text.split_at(19);
+        for (i, separator) in [(4, b'-'), (7, b'-'), (10, b'T'), (13, b':'), (16, b':')] {
+            if date[i] != separator {
+                return Err(ParseError::InvalidFormat);
+            }
         }
 
-        let leap_year = Self::is_leap_year(year as u16);
-
-        let days_from_year_start =
-            elapsed_days_without_leap_day_in_past_3_years - years_elapsed * 365 - years_elapsed / 4;
-        let mut month = 1;
-        let mut day = days_from_year_start;
-        let mut total_days_in_month = Self::days_in_month(month, leap_year) as u32;
-        while day >= total_days_in_month {
-            day -= total_days_in_month;
-            month += 1;
-            total_days_in_month = Self::days_in_month(month, leap_year) as u32;
+        let year = Self::parse_number(&date[0..4])? as u16;
+        let month = Self::parse_number(&date[5..7])? as u8;
+        let day = Self::parse_number(&date[8..10])? as u8;
+        let hours = Self::parse_number(&date[11..13])? as u8;
+        let minutes = Self::parse_number(&date[14..16])? as u8;
+        let seconds = Self::parse_number(&date[17..19])? as u8;
+        let offset_minutes = Self::parse_offset(offset)?;
+
+        // the base year is not checked, the time is not in UTC
+        Self::validate(year, hours, minutes, seconds, day, month, year)
+            .map_err(|_| ParseError::OutOfRange)?;
+
+        let local_seconds = Self::days_from_civil(day, month, year) as i64 * SECONDS_IN_DAY as i64
+            + (hours as u32 * 60 * 60 + minutes as u32 * 60 + seconds as u32) as i64;
+        let base_seconds = Self::days_from_civil(1, 1, base_year) as i64 * SECONDS_IN_DAY as i64;
+        let ticks = local_seconds - offset_minutes as i64 * 60 - base_seconds;
+        let ticks = u32::try_from(ticks).map_err(|_| ParseError::OutOfRange)?;
+
+        let mut calendar = Self::from_ticks(base_year, ticks);
+        if offset_minutes != 0 {
+            calendar.set_time_zone(TimeZone::new(offset_minutes, DstRule::None));
         }
-        day += 1;
+        Ok(calendar)
+    }
+
+    /// Checks the date exists and that it can be represented
+    /// by ticks from the base year.
+    fn validate(
+        base_year: u16,
+        hours: u8,
+        minutes: u8,
+        seconds: u8,
+        day: u8,
+        month: u8,
+        year: u16,
+    ) -> Result<(), CalendarError> {
+        if hours > 23 || minutes > 59 || seconds > 59 {
+            return Err(CalendarError::InvalidTime);
+        }
+
+        if base_year == 0 || year < base_year {
+            return Err(CalendarError::InvalidYear);
+        }
+
+        let days_in_month = Self::days_in_month(month, Self::is_leap_year(year))?;
+        if day == 0 || day > days_in_month {
+            return Err(CalendarError::InvalidDay);
+        }
+
+        let days = Self::days_from_civil(day, month, year) - Self::days_from_civil(1, 1, base_year);
+        let ticks = days as u64 * SECONDS_IN_DAY as u64
+            + (hours as u32 * 60 * 60 + minutes as u32 * 60 + seconds as u32) as u64;
+        if ticks > u32::MAX as u64 {
+            return Err(CalendarError::TicksOverflow);
+        }
+
+        Ok(())
+    }
+
+    /// Parses the offset following the date, in minutes.
+    fn parse_offset(offset: &[u8]) -> Result<i16, ParseError> {
+        let sign = match offset {
+            [] | [b'Z'] => return Ok(0),
+            [b'+', _, _, b':', _, _] => 1,
+            [b'-', _, _, b':', _, _] => -1,
+            _ => return Err(ParseError::InvalidFormat),
+        };
+
+        let hours = Self::parse_number(&offset[1..3])?;
+        let minutes = Self::parse_number(&offset[4..6])?;
+        if hours > 23 || minutes > 59 {
+            return Err(ParseError::OutOfRange);
+        }
+
+        Ok(sign * (hours * 60 + minutes) as i16)
+    }
+
+    /// Parses ASCII digits, there may be no sign.
+    fn parse_number(digits: &[u8]) -> Result<u32, ParseError> {
+        digits.iter().try_fold(0, |number, &digit| match digit {
+            b'0'..=b'9' => Ok(number * 10 + (digit - b'0') as u32),
+            _ => Err(ParseError::InvalidFormat),
+        })
+    }
+
+    /// Creates calendar for the given day, see [days_from_civil](`Calendar::days_from_civil`),
+    /// and time of the day.
+    /// Dates after [LAST_DAYS] are saturated to its last second.
+    fn from_days(base_year: u16, days: u32, seconds_in_day: u32, ticks: u32) -> Self {
+        let (days, seconds_in_day) = if days > LAST_DAYS {
+            (LAST_DAYS, SECONDS_IN_DAY - 1)
+        } else {
+            (days, seconds_in_day)
+        };
+        let (day, month, year) = Self::civil_from_days(days);
 
         Self {
             base_year,
-            seconds: seconds as u8,
-            minutes: minutes as u8,
-            hours: hours as u8,
-            day: day as u8,
-            month: month as u8,
-            year: year as u16,
+            seconds: (seconds_in_day % 60) as u8,
+            minutes: (seconds_in_day / 60 % 60) as u8,
+            hours: (seconds_in_day / 60 / 60) as u8,
+            day,
+            month,
+            year,
             frozen: false,
-            ticks: seconds,
+            ticks,
+            time_zone: TimeZone::UTC,
+            utc_offset: 0,
+            next_transition: None,
         }
     }
 
+    /// Converts the date into seconds elapsed since 1. 1. 1970 00:00:00 UTC.
+    /// None if the date cannot be represented, ie. it's before 1970,
+    /// or after 7. 2. 2106 06:28:15.
+    pub fn to_unix_timestamp(&self) -> Option<u32> {
+        let days =
+            Self::days_from_civil(self.day, self.month, self.year).checked_sub(UNIX_EPOCH_DAYS)?;
+        days.checked_mul(SECONDS_IN_DAY)?
+            .checked_add(self.seconds_in_day())
+    }
+
     /// Converts the date into ticks, elapsed seconds
     /// from base year, that was specified upon creation of the calendar.
+    /// The date is in local time, the ticks are in UTC.
     pub fn to_ticks(&self) -> u32 {
-        let mut ticks = 0u32;
-
-        ticks += self.seconds as u32;
-        ticks += self.minutes as u32 * 60;
-        ticks += self.hours as u32 * 60 * 60;
-        ticks += (self.day - 1) as u32 * 24 * 60 * 60;
-        ticks +=
-            Self::days_in_year(self.month, Self::is_leap_year(self.year)) as u32 * 24 * 60 * 60;
-        let elapsed_years = self.year as u32 - self.base_year as u32;
-        ticks += (elapsed_years * 365 + elapsed_years / 4) * 24 * 60 * 60;
-
-        for year in max(self.year - 3, self.base_year)..=self.year - 1 {
-            ticks += if Self::is_leap_year(year) {
-                24 * 60 * 60
-            } else {
-                0
-            };
+        let local_ticks = self.local_ticks();
+        if self.time_zone == TimeZone::UTC {
+            return local_ticks;
+        }
+
+        let to_utc = |offset: i32| (local_ticks as i64 - offset as i64).clamp(0, u32::MAX as i64);
+
+        // the current offset is used if it's valid for the date,
+        // that makes the repeated hour after daylight saving time
+        // ends map correctly. Otherwise, the date might have been set
+        // to the other side of a transition, the offset has to be found for it.
+        let current = to_utc(self.utc_offset);
+        if self.offset_at(current as u32).0 == self.utc_offset {
+            return current as u32;
         }
 
-        ticks
+        let standard_time = to_utc(self.time_zone.standard_offset());
+        to_utc(self.offset_at(standard_time as u32).0) as u32
+    }
+
+    /// Ticks of the local time, as if the calendar was in UTC.
+    /// Dates before the base year are clamped to it,
+    /// dates too far after it to the maximum ticks.
+    fn local_ticks(&self) -> u32 {
+        let days = Self::days_from_civil(self.day, self.month, self.year)
+            .saturating_sub(Self::days_from_civil(1, 1, self.base_year));
+        days.saturating_mul(SECONDS_IN_DAY)
+            .saturating_add(self.seconds_in_day())
     }
 
     /// Like Calendar::to_ticks, but the ticks may get
@@ -170,15 +403,163 @@ impl Calendar {
 
         let day_elapsed = hour_elapsed && self.hours == 0;
         let day = self.day - 1 + if day_elapsed { 1 } else { 0 };
-        let days_in_month = Self::days_in_month(self.month, Self::is_leap_year(self.year));
-        self.day = day % days_in_month + 1;
+        self.day = day % Self::month_days(self.month, self.year) + 1;
 
         let month_elapsed = day_elapsed && self.day == 1;
         self.month = (self.month - 1 + if month_elapsed { 1 } else { 0 }) % 12 + 1;
         let year_elapsed = month_elapsed && self.month == 1;
-        self.year += if year_elapsed { 1 } else { 0 };
+        self.year = self.year.saturating_add(if year_elapsed { 1 } else { 0 });
+
+        self.ticks = self.ticks.saturating_add(1);
+
+        if let Some(transition) = self.next_transition {
+            if self.ticks >= transition {
+                self.update_time_zone();
+            }
+        }
+    }
+
+    /// Moves the calendar by the given seconds, forwards or backwards.
+    /// The date will not get outside of the range of the ticks.
+    pub fn add_seconds(&mut self, seconds: i64) {
+        self.ticks = (self.to_ticks() as i64 + seconds).clamp(0, u32::MAX as i64) as u32;
+        self.update_time_zone();
+    }
+
+    /// Moves the calendar by the given days, forwards or backwards,
+    /// keeping the local time of day.
+    pub fn add_days(&mut self, days: i32) {
+        let days = Self::days_from_civil(self.day, self.month, self.year) as i64 + days as i64;
+        let (day, month, year) = Self::civil_from_days(days.clamp(0, LAST_DAYS as i64) as u32);
+
+        self.day = day;
+        self.month = month;
+        self.year = year;
+        self.sync_ticks();
+    }
+
+    /// Moves the calendar by the given months, forwards or backwards,
+    /// keeping the day of the month and the local time of day.
+    /// The day gets clamped to the days in the resulting month,
+    /// ie. 31. 1. moved by a month is 28. 2. or 29. 2.
+    pub fn add_months(&mut self, months: i32) {
+        let months = self.year as i64 * 12 + (self.month - 1) as i64 + months as i64;
+        // year 0 is not supported by days_from_civil
+        let months = months.clamp(12, u16::MAX as i64 * 12 + 11);
+
+        self.year = (months / 12) as u16;
+        self.month = (months % 12) as u8 + 1;
+        self.day = self.day.min(Self::month_days(self.month, self.year));
+        self.sync_ticks();
+    }
+
+    /// Seconds elapsed from the other calendar to this one,
+    /// negative if the other one is later.
+    pub fn seconds_since(&self, other: &Calendar) -> i64 {
+        self.utc_seconds() - other.utc_seconds()
+    }
+
+    pub fn time_zone(&self) -> TimeZone {
+        self.time_zone
+    }
+
+    /// Changes the time zone, keeping the UTC time.
+    /// The date, as returned by the getters, is local time.
+    pub fn set_time_zone(&mut self, time_zone: TimeZone) {
+        self.time_zone = time_zone;
+        self.update_time_zone();
+    }
+
+    /// Current offset of the local time from UTC, in seconds.
+    pub fn utc_offset(&self) -> i32 {
+        self.utc_offset
+    }
+
+    /// Updates the ticks after the local date was changed,
+    /// along with the UTC offset. The setters don't update them,
+    /// call this after the date is set.
+    pub fn sync_ticks(&mut self) {
+        self.ticks = self.to_ticks();
+        self.update_time_zone();
+    }
+
+    /// Seconds from 1. 3. 0000 00:00:00 UTC,
+    /// see [days_from_civil](`Calendar::days_from_civil`).
+    fn utc_seconds(&self) -> i64 {
+        Self::days_from_civil(self.day, self.month, self.year) as i64 * SECONDS_IN_DAY as i64
+            + self.seconds_in_day() as i64
+            - self.utc_offset as i64
+    }
+
+    /// Finds out the current UTC offset and the next daylight saving time transition
+    /// from the ticks, updating the local date accordingly.
+    fn update_time_zone(&mut self) {
+        (self.utc_offset, self.next_transition) = self.offset_at(self.ticks);
+
+        // the local date may get before the base year by the offset
+        let local_ticks = self.ticks as i64 + self.utc_offset as i64;
+        let days = Self::days_from_civil(1, 1, self.base_year) as i64
+            + local_ticks.div_euclid(SECONDS_IN_DAY as i64);
+        let local = Self::from_days(
+            self.base_year,
+            days as u32,
+            local_ticks.rem_euclid(SECONDS_IN_DAY as i64) as u32,
+            self.ticks,
+        );
+        self.hours = local.hours;
+        self.minutes = local.minutes;
+        self.seconds = local.seconds;
+        self.day = local.day;
+        self.month = local.month;
+        self.year = local.year;
+    }
+
+    /// UTC offset at the ticks, along with the ticks of the next transition.
+    /// Daylight saving time is only known for u32 Unix timestamps,
+    /// from 1970 to 2106, the standard offset is used outside of them.
+    pub fn offset_at(&self, ticks: u32) -> (i32, Option<u32>) {
+        let standard_offset = self.time_zone.standard_offset();
+        if self.time_zone.dst() == DstRule::None {
+            return (standard_offset, None);
+        }
+
+        let base = self.base_unix_timestamp();
+        let timestamp = base + ticks as i64;
+        match u32::try_from(timestamp) {
+            Ok(timestamp) => (
+                self.time_zone.offset(timestamp),
+                self.time_zone
+                    .next_transition(timestamp)
+                    .and_then(|transition| u32::try_from(transition as i64 - base).ok()),
+            ),
+            // the offset has to be found again once 1970 is reached
+            Err(_) if timestamp < 0 => (standard_offset, u32::try_from(-base).ok()),
+            Err(_) => (standard_offset, None),
+        }
+    }
+
+    /// Sign, hours and minutes of the UTC offset,
+    /// None if the calendar is in UTC.
+    fn utc_offset_parts(&self) -> Option<(char, u32, u32)> {
+        let sign = match self.utc_offset {
+            0 => return None,
+            offset if offset < 0 => '-',
+            _ => '+',
+        };
+        let minutes = self.utc_offset.unsigned_abs() / 60;
+
+        Some((sign, minutes / 60, minutes % 60))
+    }
 
-        self.ticks += 1;
+    fn seconds_in_day(&self) -> u32 {
+        self.hours as u32 * 60 * 60 + self.minutes as u32 * 60 + self.seconds as u32
+    }
+
+    /// Unix timestamp of the start of the base year,
+    /// negative for base years before 1970.
+    fn base_unix_timestamp(&self) -> i64 {
+        (Self::days_from_civil(1, 1, self.base_year) as i64 - UNIX_EPOCH_DAYS as i64)
+            * SECONDS_IN_DAY as i64
     }
 
     pub fn hours(&self) -> u8 {
@@ -205,8 +586,37 @@ impl Calendar {
         self.year
     }
 
+    /// Day of the year, 1 for the first of January.
+    pub fn day_of_year(&self) -> u16 {
+        (Self::days_from_civil(self.day, self.month, self.year)
+            - Self::days_from_civil(1, 1, self.year)
+            + 1) as u16
+    }
+
+    /// Day of the week.
+    pub fn weekday(&self) -> Weekday {
+        Self::weekday_of_days(Self::days_from_civil(self.day, self.month, self.year) as i64)
+    }
+
+    /// Day of the week in local time at the given ticks.
+    pub fn weekday_at(&self, ticks: u32) -> Weekday {
+        let (utc_offset, _) = self.offset_at(ticks);
+        let local_ticks = ticks as i64 + utc_offset as i64;
+        let days = Self::days_from_civil(1, 1, self.base_year) as i64
+            + local_ticks.div_euclid(SECONDS_IN_DAY as i64);
+
+        Self::weekday_of_days(days)
+    }
+
+    /// Day of the week of the given days
+    /// as counted by [days_from_civil](`Calendar::days_from_civil`).
+    fn weekday_of_days(days: i64) -> Weekday {
+        // 1. 3. 0000 was a Wednesday
+        Weekday::from_days_after_monday((days + 2).rem_euclid(7) as u8)
+    }
+
     /// Sets the current hour of the day,
-    /// gets clamped to 0 - 24.
+    /// gets clamped to 0 - 23.
     pub fn set_hours(&mut self, hours: u8) {
         self.hours = hours.clamp(0, 23);
     }
@@ -226,27 +636,84 @@ impl Calendar {
     /// Sets the current day of the month,
     /// gets clamped to 1 - days in the month.
     pub fn set_day(&mut self, day: u8) {
-        self.day = day.clamp(
-            1,
-            Self::days_in_month(self.month, Self::is_leap_year(self.year)),
-        );
+        self.day = day.clamp(1, Self::month_days(self.month, self.year));
     }
 
-    /// Sets the current day of the month,
-    /// gets clamped to 1 - days in the month.
+    /// Sets the current month,
+    /// gets clamped to 1 - 12.
+    /// The day gets clamped to the days in the new month.
     pub fn set_month(&mut self, month: u8) {
         self.month = month.clamp(1, 12);
+        self.day = self.day.min(Self::month_days(self.month, self.year));
     }
 
     /// Sets the current year,
     /// The minimum is the base year specified
     /// upon Calendar creation. Lower year
     /// will be adjusted to base year.
+    /// The day gets clamped to the days in the month,
+    /// as 29. 2. might not exist in the new year.
     pub fn set_year(&mut self, year: u16) {
         self.year = max(year, self.base_year);
+        self.day = self.day.min(Self::month_days(self.month, self.year));
     }
 
-    fn is_leap_year(year: u16) -> bool {
+    /// Sets the current hour of the day,
+    /// fails if it's over 23.
+    pub fn try_set_hours(&mut self, hours: u8) -> Result<(), CalendarError> {
+        self.try_set(|calendar| calendar.hours = hours)
+    }
+
+    /// Sets the current minute of the hour,
+    /// fails if it's over 59.
+    pub fn try_set_minutes(&mut self, minutes: u8) -> Result<(), CalendarError> {
+        self.try_set(|calendar| calendar.minutes = minutes)
+    }
+
+    /// Sets the current seconds of the minute,
+    /// fails if they're over 59.
+    pub fn try_set_seconds(&mut self, seconds: u8) -> Result<(), CalendarError> {
+        self.try_set(|calendar| calendar.seconds = seconds)
+    }
+
+    /// Sets the current day of the month,
+    /// fails if the day is not in the month.
+    pub fn try_set_day(&mut self, day: u8) -> Result<(), CalendarError> {
+        self.try_set(|calendar| calendar.day = day)
+    }
+
+    /// Sets the current month, fails if it doesn't exist,
+    /// or if the current day is not in it.
+    pub fn try_set_month(&mut self, month: u8) -> Result<(), CalendarError> {
+        self.try_set(|calendar| calendar.month = month)
+    }
+
+    /// Sets the current year, fails if it's before the base year,
+    /// too far after it, or if the current day is not in it.
+    pub fn try_set_year(&mut self, year: u16) -> Result<(), CalendarError> {
+        self.try_set(|calendar| calendar.year = year)
+    }
+
+    /// Changes the values by the given function, if they are
+    /// valid afterwards, otherwise the calendar is left unchanged.
+    fn try_set(&mut self, set: impl FnOnce(&mut Self)) -> Result<(), CalendarError> {
+        let mut calendar = self.clone();
+        set(&mut calendar);
+        Self::validate(
+            calendar.base_year,
+            calendar.hours,
+            calendar.minutes,
+            calendar.seconds,
+            calendar.day,
+            calendar.month,
+            calendar.year,
+        )?;
+
+        *self = calendar;
+        Ok(())
+    }
+
+    pub fn is_leap_year(year: u16) -> bool {
         matches!(year % 4, 0 if year % 100 != 0 || year % 400 == 0)
     }
 
@@ -254,26 +721,123 @@ impl Calendar {
         self.frozen = true;
     }
 
+    /// Lets the time run again. The date might have been changed
+    /// while frozen, the ticks and the UTC offset are updated from it.
     pub fn unfreeze(&mut self) {
         self.frozen = false;
+        self.sync_ticks();
     }
 
-    fn days_in_month(month: u8, leap_year: bool) -> u8 {
+    /// Days in the given month, fails if the month does not exist.
+    pub fn days_in_month(month: u8, leap_year: bool) -> Result<u8, CalendarError> {
         match month {
-            2 if leap_year => 29,
-            2 => 28,
-            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
-            4 | 6 | 9 | 11 => 30,
-            _ => panic!("Month {month} does not exist, cannot get days in that month."),
+            2 if leap_year => Ok(29),
+            2 => Ok(28),
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => Ok(31),
+            4 | 6 | 9 | 11 => Ok(30),
+            _ => Err(CalendarError::InvalidMonth),
         }
     }
 
-    fn days_in_year(before_month: u8, leap_year: bool) -> u16 {
-        let mut days_in_year = 0u16;
-        for i in 1..before_month {
-            days_in_year += Self::days_in_month(i, leap_year) as u16;
+    /// Days in the month of the year, for a month that is known to exist,
+    /// as the month of a calendar is.
+    fn month_days(month: u8, year: u16) -> u8 {
+        Self::days_in_month(month, Self::is_leap_year(year)).unwrap_or(31)
+    }
+
+    /// Days elapsed from 1. 3. 0000 to the given date
+    /// of the proleptic Gregorian calendar.
+    /// Counting from March puts the leap day at the end of the year.
+    /// See <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+    const fn days_from_civil(day: u8, month: u8, year: u16) -> u32 {
+        let year = year as u32 - if month <= 2 { 1 } else { 0 };
+        let era = year / 400;
+        let year_of_era = year - era * 400;
+        let month_from_march = if month > 2 { month - 3 } else { month + 9 } as u32;
+        let day_of_year = (153 * month_from_march + 2) / 5 + day as u32 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146097 + day_of_era
+    }
+
+    /// Inverse of [days_from_civil](`Calendar::days_from_civil`),
+    /// returns day, month and year. The days must not be after [LAST_DAYS].
+    /// See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+    fn civil_from_days(days: u32) -> (u8, u8, u16) {
+        let era = days / 146097;
+        let day_of_era = days - era * 146097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_from_march = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+        let month = if month_from_march < 10 {
+            month_from_march + 3
+        } else {
+            month_from_march - 9
+        };
+        let year = era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+
+        (day as u8, month as u8, year as u16)
+    }
+}
+
+impl PartialEq for Calendar {
+    fn eq(&self, other: &Self) -> bool {
+        self.utc_seconds() == other.utc_seconds()
+    }
+}
+
+impl Eq for Calendar {}
+
+impl PartialOrd for Calendar {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Calendar {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.utc_seconds().cmp(&other.utc_seconds())
+    }
+}
+
+/// Formats the date as ISO 8601, `YYYY-MM-DDTHH:MM:SS`
+/// followed by `Z` in UTC, or the offset from UTC, ie. `+01:00`.
+impl fmt::Display for Calendar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hours, self.minutes, self.seconds
+        )?;
+
+        match self.utc_offset_parts() {
+            None => write!(f, "Z"),
+            Some((sign, hours, minutes)) => write!(f, "{sign}{hours:02}:{minutes:02}"),
         }
+    }
+}
 
-        days_in_year
+/// Same format as [Display](`fmt::Display`).
+impl defmt::Format for Calendar {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "{=u16:04}-{=u8:02}-{=u8:02}T{=u8:02}:{=u8:02}:{=u8:02}",
+            self.year,
+            self.month,
+            self.day,
+            self.hours,
+            self.minutes,
+            self.seconds
+        );
+
+        match self.utc_offset_parts() {
+            None => defmt::write!(f, "Z"),
+            Some((sign, hours, minutes)) => {
+                defmt::write!(f, "{=char}{=u32:02}:{=u32:02}", sign, hours, minutes)
+            }
+        }
     }
 }
@@ -1,24 +1,29 @@
 use alloc::boxed::Box;
-use stm32f1xx_hal::rtc::Rtc;
+use stm32f1xx_hal::{backup_domain::BackupDomain, pac, rtc::Rtc};
 
 use crate::{
     brightness_manager::BrightnessManager,
     button::ButtonState,
+    clock_display::DisplayPart,
     clock_display_viewer::ClockDisplayViewer,
-    clock_state::ClockState, app_mode::{ClockAppMode, ClockAppModes, default_app_mode::DefaultAppMode, edit_app_mode::EditAppMode},
+    clock_state::ClockState, rtc_time::RtcTime, app_mode::{ClockAppMode, ClockAppModes, default_app_mode::DefaultAppMode, edit_app_mode::EditAppMode, stopwatch_app_mode::StopwatchAppMode, countdown_app_mode::CountdownAppMode, alarm_edit_app_mode::AlarmEditAppMode, settings_app_mode::SettingsAppMode},
 };
 
 pub struct ClockApp {
     rtc: Rtc,
+    backup: BackupDomain,
     display: ClockDisplayViewer,
     state: ClockState,
     modes: [Box<dyn ClockAppMode + Send>; core::mem::variant_count::<ClockAppModes>()],
     brightness: BrightnessManager,
     current_mode: ClockAppModes,
+    /// Button that changed the mode, while it's held.
+    held_button: Option<usize>,
 }
 
 pub struct AppState<'a> {
     pub rtc: &'a mut Rtc,
+    pub backup: &'a mut BackupDomain,
     pub display: &'a mut ClockDisplayViewer,
     pub state: &'a mut ClockState,
     pub brightness: &'a mut BrightnessManager,
@@ -27,54 +32,159 @@ pub struct AppState<'a> {
 
 pub enum ClockInterrupt {
     Rtc,
+    Alarm,
     DisplayTimer,
 }
 
+/// Flags of the RTC, telling what raised its interrupts.
+///
+/// The alarm raises the RTC interrupt too, not only
+/// the RTC alarm interrupt, so the flags have to be checked.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct RtcFlags {
+    bits: u32,
+}
+
+impl RtcFlags {
+    const SECOND: u32 = 1 << 0;
+    const ALARM: u32 = 1 << 1;
+
+    /// Flags from the bits of the RTC control register, CRL.
+    pub fn from_bits(bits: u32) -> Self {
+        Self { bits }
+    }
+
+    /// Reads the flags of the RTC.
+    pub fn read() -> Self {
+        // reading the control register has no side effects,
+        // the flags are cleared through the Rtc
+        Self::from_bits(unsafe { (*pac::RTC::ptr()).crl.read().bits() })
+    }
+
+    /// Whether a second elapsed.
+    pub fn second(&self) -> bool {
+        self.bits & Self::SECOND != 0
+    }
+
+    /// Whether the counter reached the alarm.
+    pub fn alarm(&self) -> bool {
+        self.bits & Self::ALARM != 0
+    }
+}
+
 impl ClockApp {
-    pub fn new(rtc: Rtc, display: ClockDisplayViewer, state: ClockState) -> Self {
-        Self {
+    /// Alarms are loaded from the backup domain
+    /// and the RTC alarm is set to the next one of them.
+    pub fn new(
+        rtc: Rtc,
+        backup: BackupDomain,
+        display: ClockDisplayViewer,
+        mut state: ClockState,
+    ) -> Self {
+        state.mut_alarms().load(&backup);
+
+        let mut app = Self {
             rtc,
+            backup,
             display,
             state,
             current_mode: ClockAppModes::NormalMode,
             modes: [
                 Box::new(DefaultAppMode::new()),
-                Box::new(EditAppMode::new())
+                Box::new(EditAppMode::new()),
+                Box::new(StopwatchAppMode::new()),
+                Box::new(CountdownAppMode::new()),
+                Box::new(AlarmEditAppMode::new()),
+                Box::new(SettingsAppMode::new()),
             ],
             brightness: BrightnessManager::new(),
-        }
+            held_button: None,
+        };
+
+        app.schedule_alarm();
+        app
     }
 
     pub fn interrupt(&mut self, interrupt: ClockInterrupt) {
         match interrupt {
             ClockInterrupt::Rtc => {
-                self.state.second_elapsed();
-                self.rtc.clear_second_flag();
+                let flags = RtcFlags::read();
+
+                if flags.second() {
+                    let utc_offset = self.state.calendar().utc_offset();
+                    self.state.second_elapsed();
+                    self.rtc.clear_second_flag();
+
+                    // the alarms are in local time
+                    if self.state.calendar().utc_offset() != utc_offset {
+                        self.schedule_alarm();
+                    }
+                }
+
+                // the alarm flag has to be cleared here,
+                // the interrupt would be raised again otherwise
+                if flags.alarm() {
+                    self.alarm_elapsed();
+                }
+            }
+            ClockInterrupt::Alarm => {
+                // the RTC interrupt might have handled it already
+                if RtcFlags::read().alarm() {
+                    self.alarm_elapsed();
+                }
             }
             ClockInterrupt::DisplayTimer => {
+                self.state.update(RtcTime::now(&self.rtc));
                 let _ = self.display.update(&self.state);
                 self.brightness.apply_brightness(&mut self.display);
 
                 let mut mode = self.current_mode;
                 let app_state = AppState {
                     rtc: &mut self.rtc,
+                    backup: &mut self.backup,
                     display: &mut self.display,
                     state: &mut self.state,
                     brightness: &mut self.brightness,
                     current_mode: &mut mode,
                 };
                 self.modes[self.current_mode as usize].update(app_state);
+                self.display.update_transition(&self.state);
+
+                if self.alerting() && !self.alert_blink() {
+                    let display = self.display.clock_display();
+                    display.hide(DisplayPart::Whole);
+                    display.set_colon(false);
+                }
             }
         }
     }
 
     pub fn handle_button(&mut self, index: usize, state: ButtonState) {
+        if self.alerting() {
+            // the press acknowledges the alert,
+            // it should not get to the mode
+            if state == ButtonState::JustPressed {
+                self.acknowledge_alert(index);
+            }
+            return;
+        }
+
+        // holding the button that changed the mode
+        // should not do anything in the new mode
+        if self.held_button == Some(index) {
+            if state == ButtonState::LongPress {
+                return;
+            }
+            self.held_button = None;
+        }
+
         let mut mode = self.current_mode;
         let current_mode = self.modes[self.current_mode as usize].as_mut();
 
         {
             let app_state = AppState {
                 rtc: &mut self.rtc,
+                backup: &mut self.backup,
                 display: &mut self.display,
                 state: &mut self.state,
                 brightness: &mut self.brightness,
@@ -87,18 +197,80 @@ impl ClockApp {
         if self.current_mode != mode {
             let mut temp_mode = mode;
             {
-                current_mode.stop(AppState { rtc: &mut self.rtc, display: &mut self.display, state: &mut self.state, brightness: &mut self.brightness, current_mode: &mut temp_mode });
+                current_mode.stop(AppState { rtc: &mut self.rtc, backup: &mut self.backup, display: &mut self.display, state: &mut self.state, brightness: &mut self.brightness, current_mode: &mut temp_mode });
             }
 
             self.current_mode = temp_mode;
+            self.held_button = Some(index);
 
             let current_mode = self.modes[self.current_mode as usize].as_mut();
-            current_mode.run(AppState { rtc: &mut self.rtc, display: &mut self.display, state: &mut self.state, brightness: &mut self.brightness, current_mode: &mut temp_mode });
+            current_mode.run(AppState { rtc: &mut self.rtc, backup: &mut self.backup, display: &mut self.display, state: &mut self.state, brightness: &mut self.brightness, current_mode: &mut temp_mode });
+
+            // the time or the alarms might have been edited
+            self.schedule_alarm();
+        }
+    }
+
+    /// Rings if an alarm is due, then sets the RTC alarm to the next one.
+    fn alarm_elapsed(&mut self) {
+        self.rtc.clear_alarm_flag();
+        let ticks = self.rtc.current_time();
+        if self.state.alarms().due(ticks, self.state.calendar()) {
+            self.state.mut_alarms().ring();
+        }
+        self.schedule_alarm();
+    }
+
+    /// Sets the RTC alarm to the next alarm that should go off,
+    /// or disables it if there is none.
+    pub fn schedule_alarm(&mut self) {
+        let ticks = self.rtc.current_time();
+        let next_alarm = self.state.alarms().next_alarm_ticks(ticks, self.state.calendar());
+
+        match next_alarm {
+            Some(alarm) => {
+                self.rtc.set_alarm(alarm);
+                self.rtc.listen_alarm();
+            }
+            None => self.rtc.unlisten_alarm(),
+        }
+    }
+
+    /// Ringing alarm is dismissed by the fourth button,
+    /// other buttons snooze it.
+    /// Expired countdown is acknowledged by any button.
+    fn acknowledge_alert(&mut self, button: usize) {
+        if self.state.alarms().ringing() {
+            let ticks = self.rtc.current_time();
+            if button == 3 {
+                self.state.mut_alarms().dismiss();
+            } else {
+                self.state.mut_alarms().snooze(ticks);
+            }
+            self.schedule_alarm();
+        } else {
+            self.state.mut_countdown().acknowledge();
         }
     }
 
+    /// Whether something, ie. expired countdown or ringing alarm,
+    /// requires user's attention.
+    pub fn alerting(&self) -> bool {
+        self.state.countdown().expired() || self.state.alarms().ringing()
+    }
+
+    /// When alerting, the display and LEDs are flashing.
+    /// Returns whether they should be on at the moment.
+    pub fn alert_blink(&self) -> bool {
+        self.alerting() && self.state.calendar().seconds() % 2 == 0
+    }
+
     pub fn display(&mut self) -> &mut ClockDisplayViewer {
         &mut self.display
     }
+
+    pub fn brightness(&mut self) -> &mut BrightnessManager {
+        &mut self.brightness
+    }
 }
 
@@ -1,6 +1,9 @@
-use stm32f1xx_hal::timer;
+use stm32f1xx_hal::{backup_domain::BackupDomain, timer};
 
-use crate::{display::Display, seven_segments::SevenSegments};
+use crate::{
+    display::Display, number_format::NumberFormat, rtc_calibration::CALIBRATION_REGISTER,
+    seven_segments::SevenSegments,
+};
 
 const MAIN_DISPLAY_OFFSET: usize = 2;
 const MAIN_DISPLAY_SIZE: usize = 4;
@@ -12,6 +15,20 @@ const SIDE_DISPLAY_2_SIZE: usize = 2;
 const COLON_DIGIT_1: usize = 3;
 const COLON_DIGIT_2: usize = 4;
 
+/// Digits with a dot of their own, the colon digits
+/// have a dot of the colon each.
+const DOT_DIGITS: [usize; 4] = [0, 1, 6, 7];
+
+/// Backup data register with the hour format, in its high byte,
+/// shared with the correction of the RTC drift in the low one.
+const HOUR_FORMAT_REGISTER: usize = CALIBRATION_REGISTER;
+const HOUR_FORMAT_SHIFT: u16 = 8;
+
+/// Digit whose dot marks PM in 12-hour format,
+/// the last digit of the first side display,
+/// right before the hours on the main display.
+const PM_DIGIT: usize = 1;
+
 pub struct ClockDisplay {
     display: Display<8>,
     colon: bool,
@@ -25,6 +42,56 @@ pub enum DisplayPart {
     SideDisplay2,
 }
 
+/// How the hours of the day are shown.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum HourFormat {
+    /// 00 - 23
+    TwentyFourHour,
+    /// 1 - 12, PM is marked by a dot.
+    TwelveHour,
+}
+
+impl HourFormat {
+    /// Converts hours of the day, 0 - 23, to the hours to be shown
+    /// and whether they are PM. Never PM in 24-hour format.
+    pub fn hours(&self, hours: u8) -> (u8, bool) {
+        match self {
+            HourFormat::TwentyFourHour => (hours, false),
+            HourFormat::TwelveHour => match hours % 12 {
+                0 => (12, hours >= 12),
+                hours_of_half => (hours_of_half, hours >= 12),
+            },
+        }
+    }
+
+    /// The other format.
+    pub fn toggled(&self) -> Self {
+        match self {
+            HourFormat::TwentyFourHour => HourFormat::TwelveHour,
+            HourFormat::TwelveHour => HourFormat::TwentyFourHour,
+        }
+    }
+
+    /// Reads the format from the backup domain,
+    /// None if it was not saved, ie. after the backup domain was reset.
+    pub fn load(backup: &BackupDomain) -> Option<Self> {
+        match backup.read_data_register_low(HOUR_FORMAT_REGISTER) >> HOUR_FORMAT_SHIFT {
+            1 => Some(HourFormat::TwentyFourHour),
+            2 => Some(HourFormat::TwelveHour),
+            _ => None,
+        }
+    }
+
+    pub fn save(&self, backup: &mut BackupDomain) {
+        let format = match self {
+            HourFormat::TwentyFourHour => 1,
+            HourFormat::TwelveHour => 2,
+        };
+        let data = backup.read_data_register_low(HOUR_FORMAT_REGISTER) & 0xFF;
+        backup.write_data_register_low(HOUR_FORMAT_REGISTER, data | format << HOUR_FORMAT_SHIFT);
+    }
+}
+
 #[derive(Debug)]
 pub enum DisplayError {
     DoesNotFit,
@@ -102,18 +169,184 @@ impl ClockDisplay {
         Ok(())
     }
 
+    /// Shows hours of the day, 0 - 23, on two digits from the offset.
+    /// In 12-hour format, there is no leading zero and PM
+    /// is marked by the dot of the last digit of the first side display,
+    /// see [set_pm](`ClockDisplay::set_pm`). The dot is left alone in 24-hour format.
+    pub fn show_hours_at(
+        &mut self,
+        offset: usize,
+        hours: u8,
+        format: HourFormat,
+    ) -> Result<(), DisplayError> {
+        let (hours, pm) = format.hours(hours);
+        self.show_number_at(
+            offset,
+            2,
+            hours as u32,
+            format == HourFormat::TwentyFourHour,
+        )?;
+        if format == HourFormat::TwelveHour {
+            self.set_pm(pm);
+        }
+        Ok(())
+    }
+
+    /// Lights the dot marking PM. Showing anything else
+    /// on the first side display clears it.
+    pub fn set_pm(&mut self, pm: bool) {
+        self.set_dot(PM_DIGIT, pm);
+    }
+
+    /// Lights or clears the dot of the digit,
+    /// keeping its other segments.
+    pub fn set_dot(&mut self, digit: usize, dot: bool) {
+        let data = self.display.data()[digit];
+        self.display
+            .set_digit(digit, if dot { data | 0b1 } else { data & 0xFE });
+    }
+
+    /// Shows the text from the first digit of the part, one character per digit.
+    /// Characters that cannot be rendered are shown as
+    /// [UNRENDERABLE](`crate::seven_segments::UNRENDERABLE`),
+    /// see [SevenSegments::supported](`SevenSegments::supported`).
+    /// Dots and colons are merged into the previous digit,
+    /// see [text_segments](`ClockDisplay::text_segments`).
     pub fn show_text(&mut self, part: DisplayPart, text: &str) -> Result<(), DisplayError> {
         let offset = Self::get_part_offset(part);
         let size = Self::get_part_size(part);
 
-        if text.len() > size {
+        self.show_text_at(offset, size, text)
+    }
+
+    pub fn show_text_at(
+        &mut self,
+        offset: usize,
+        size: usize,
+        text: &str,
+    ) -> Result<(), DisplayError> {
+        let mut data = self.display.data();
+        Self::text_segments(offset, text, &mut data[offset..offset + size])?;
+
+        self.show_text_data(offset, size, data);
+        Ok(())
+    }
+
+    /// Shows the number formatted on the part,
+    /// see [show_formatted_at](`ClockDisplay::show_formatted_at`).
+    pub fn show_formatted(&mut self, part: DisplayPart, number: i64, format: NumberFormat) -> bool {
+        let offset = Self::get_part_offset(part);
+        let size = Self::get_part_size(part);
+
+        self.show_formatted_at(offset, size, number, format)
+    }
+
+    /// Shows the number formatted on the digits, see [NumberFormat](`NumberFormat`).
+    /// Returns whether the number fits, if it doesn't,
+    /// an indicator is shown on all the digits instead.
+    pub fn show_formatted_at(
+        &mut self,
+        offset: usize,
+        size: usize,
+        number: i64,
+        format: NumberFormat,
+    ) -> bool {
+        let mut data = self.display.data();
+        let fits = format.segments(offset, number, &mut data[offset..offset + size]);
+
+        self.show_text_data(offset, size, data);
+        fits
+    }
+
+    /// Shows the data converted from a text on the digits.
+    fn show_text_data(&mut self, offset: usize, size: usize, data: [u8; 8]) {
+        self.display.set_data(data);
+        self.update_colon();
+
+        // the dots of the text are shown no matter the colon
+        for digit in [COLON_DIGIT_1, COLON_DIGIT_2] {
+            if (offset..offset + size).contains(&digit) && data[digit] & 1 != 0 {
+                self.set_dot(digit, true);
+            }
+        }
+    }
+
+    /// Converts the text to the segments of the digits starting at the offset.
+    ///
+    /// A '.' is shown as the dot of the previous digit, if it has one,
+    /// on the colon digits it's one of the dots of the colon.
+    /// A ':' right after the first colon digit is shown as the colon,
+    /// elsewhere it's handled like a '.'. Dots that cannot be merged,
+    /// ie. at the start or after another dot, take a digit of their own.
+    /// The digits the text doesn't reach are left unchanged.
+    ///
+    /// Returns the count of the digits taken,
+    /// fails if there are more digits than the segments.
+    pub fn text_segments(
+        offset: usize,
+        text: &str,
+        segments: &mut [u8],
+    ) -> Result<usize, DisplayError> {
+        let mut digits: usize = 0;
+        // the second dot of the colon, shown on the next digit
+        let mut colon_dot = 0;
+
+        for character in text.chars() {
+            if let Some(previous) = digits.checked_sub(1) {
+                let digit = offset + previous;
+                let dot_free = segments[previous] & 1 == 0;
+
+                match character {
+                    ':' if digit == COLON_DIGIT_1 && dot_free => {
+                        segments[previous] |= 1;
+                        colon_dot = 1;
+                        if let Some(next) = segments.get_mut(digits) {
+                            *next |= 1;
+                        }
+                        continue;
+                    }
+                    '.' | ':' if Self::has_dot(digit) && dot_free => {
+                        segments[previous] |= 1;
+                        continue;
+                    }
+                    _ => (),
+                }
+            }
+
+            if digits == segments.len() {
+                return Err(DisplayError::DoesNotFit);
+            }
+
+            segments[digits] = colon_dot
+                | match character {
+                    '.' | ':' => 1,
+                    _ => SevenSegments::letter_to_segments(character),
+                };
+            colon_dot = 0;
+            digits += 1;
+        }
+
+        Ok(digits)
+    }
+
+    fn has_dot(digit: usize) -> bool {
+        DOT_DIGITS.contains(&digit) || digit == COLON_DIGIT_1 || digit == COLON_DIGIT_2
+    }
+
+    /// Shows raw segments, in the format of [SevenSegments](`SevenSegments`),
+    /// on the part, starting at its first digit.
+    pub fn show_segments(
+        &mut self,
+        part: DisplayPart,
+        segments: &[u8],
+    ) -> Result<(), DisplayError> {
+        let offset = Self::get_part_offset(part);
+        if segments.len() > Self::get_part_size(part) {
             return Err(DisplayError::DoesNotFit);
         }
 
         let mut data = self.display.data();
-        for (i, c) in text.chars().enumerate() {
-            data[offset + i] = SevenSegments::letter_to_segments(c);
-        }
+        data[offset..offset + segments.len()].copy_from_slice(segments);
 
         self.display.set_data(data);
         self.update_colon();
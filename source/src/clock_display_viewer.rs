@@ -1,9 +1,16 @@
 use crate::{
-    clock_display::{ClockDisplay, DisplayPart},
+    clock_display::{ClockDisplay, DisplayPart, HourFormat},
     clock_state::ClockState,
+    marquee::Marquee,
+    moon::MoonPhase,
+    transition::{Transition, TransitionKind, DEFAULT_DURATION_MILLIS},
 };
 use stm32f1xx_hal::timer;
 
+/// The colon blinks every second, it's shown for the first part
+/// of the second, in milliseconds, hidden for the rest of it.
+const COLON_ON_MILLIS: u16 = 500;
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 #[repr(usize)]
 pub enum DisplayView {
@@ -11,11 +18,15 @@ pub enum DisplayView {
     ClockSecondsView = 1,
     ClockDateView = 2,
     DateView = 3,
+    DateWeekdayView = 4,
+    MoonPhaseView = 5,
+    DaysUntilView = 6,
+    WorldClockView = 7,
 }
 
 impl TryFrom<usize> for DisplayView {
     fn try_from(value: usize) -> Result<Self, ()> {
-        if value <= DisplayView::DateView as usize {
+        if value <= DisplayView::WorldClockView as usize {
             unsafe { core::mem::transmute(value) }
         } else {
             Err(())
@@ -35,11 +46,16 @@ pub enum ClockPart {
     Year = 3,
     Month = 4,
     Day = 5,
+    Weekday = 6,
+    MoonPhase = 7,
+    DaysUntil = 8,
+    WorldHours = 9,
+    WorldMinutes = 10,
 }
 
 impl TryFrom<usize> for ClockPart {
     fn try_from(value: usize) -> Result<Self, ()> {
-        if value <= ClockPart::Day as usize {
+        if value <= ClockPart::WorldMinutes as usize {
             unsafe { core::mem::transmute(value) }
         } else {
             Err(())
@@ -52,6 +68,10 @@ impl TryFrom<usize> for ClockPart {
 pub struct ClockDisplayViewer {
     clock_display: ClockDisplay,
     parts: [bool; core::mem::variant_count::<ClockPart>()],
+    hour_format: HourFormat,
+    marquee: Option<Marquee>,
+    transition: Option<Transition>,
+    transition_millis: u32,
 }
 
 impl ClockDisplayViewer {
@@ -59,6 +79,10 @@ impl ClockDisplayViewer {
         Self {
             clock_display,
             parts: [false; core::mem::variant_count::<ClockPart>()],
+            hour_format: HourFormat::TwentyFourHour,
+            marquee: None,
+            transition: None,
+            transition_millis: DEFAULT_DURATION_MILLIS,
         }
     }
 
@@ -76,6 +100,77 @@ impl ClockDisplayViewer {
         }
     }
 
+    /// Format of the hours in every view showing them.
+    /// App modes showing hours should respect it too.
+    pub fn hour_format(&self) -> HourFormat {
+        self.hour_format
+    }
+
+    pub fn set_hour_format(&mut self, hour_format: HourFormat) {
+        self.hour_format = hour_format;
+    }
+
+    /// Whether the current view shows the hours in 12-hour format,
+    /// the dot right before them marks PM then.
+    fn marks_pm(&self) -> bool {
+        self.parts[ClockPart::Hours as usize] && self.hour_format == HourFormat::TwelveHour
+    }
+
+    /// Scrolls the text of the marquee over the parts shown,
+    /// they are shown again once it's finished.
+    pub fn show_marquee(&mut self, marquee: Marquee) {
+        self.marquee = Some(marquee);
+    }
+
+    pub fn hide_marquee(&mut self) {
+        self.marquee = None;
+    }
+
+    /// The marquee shown last, see [Marquee::finished](`Marquee::finished`)
+    /// to find out whether it's still scrolling.
+    pub fn marquee(&self) -> Option<&Marquee> {
+        self.marquee.as_ref()
+    }
+
+    /// Duration of the transitions started from now on.
+    pub fn transition_millis(&self) -> u32 {
+        self.transition_millis
+    }
+
+    pub fn set_transition_millis(&mut self, millis: u32) {
+        self.transition_millis = millis;
+    }
+
+    /// Animates the change from what is shown now to what is rendered next,
+    /// ie. after the view or the app mode changes.
+    /// Replaces the transition in progress, starting from its current frame.
+    pub fn start_transition(&mut self, kind: TransitionKind) {
+        let from = self.clock_display.display().data();
+        self.transition = Some(Transition::new(kind, from, self.transition_millis));
+    }
+
+    /// The transition in progress, if any.
+    pub fn transition(&self) -> Option<&Transition> {
+        self.transition.as_ref()
+    }
+
+    /// Shows the transition over everything rendered by now,
+    /// has to be called after the app mode has rendered too.
+    pub fn update_transition(&mut self, state: &ClockState) {
+        if let Some(transition) = self.transition.as_mut() {
+            if transition.update(&mut self.clock_display, state.timer().now()) {
+                self.transition = None;
+            }
+        }
+    }
+
+    /// Changes the view with a transition,
+    /// see [start_transition](`ClockDisplayViewer::start_transition`).
+    pub fn transition_to_view(&mut self, view: DisplayView, kind: TransitionKind) {
+        self.start_transition(kind);
+        self.set_current_view(view);
+    }
+
     pub fn clock_display(&mut self) -> &mut ClockDisplay {
         &mut self.clock_display
     }
@@ -103,12 +198,34 @@ impl ClockDisplayViewer {
                 self.show(ClockPart::Month);
                 self.show(ClockPart::Year);
             }
+            DisplayView::DateWeekdayView => {
+                self.show(ClockPart::Day);
+                self.show(ClockPart::Month);
+                self.show(ClockPart::Weekday);
+            }
+            DisplayView::MoonPhaseView => {
+                self.show(ClockPart::MoonPhase);
+            }
+            DisplayView::DaysUntilView => {
+                self.show(ClockPart::DaysUntil);
+            }
+            DisplayView::WorldClockView => {
+                self.show(ClockPart::Hours);
+                self.show(ClockPart::Minutes);
+                self.show(ClockPart::WorldHours);
+                self.show(ClockPart::WorldMinutes);
+            }
         }
     }
 
     pub fn update(&mut self, state: &ClockState) -> nb::Result<(), timer::Error> {
         self.clock_display.update()?;
 
+        // the parts are rendered over the new frame, not the transition
+        if let Some(transition) = &self.transition {
+            self.clock_display.display().set_data(transition.target());
+        }
+
         for (i, show) in self.parts.iter().enumerate().filter(|(_, x)| **x) {
             if !show {
                 continue;
@@ -117,13 +234,17 @@ impl ClockDisplayViewer {
             let part: ClockPart = ClockPart::try_from(i).unwrap();
             match part {
                 ClockPart::Day => {
-                    self.clock_display
-                        .show_ordinal(
-                            DisplayPart::SideDisplay1,
-                            state.calendar().day() as u32,
-                            true,
-                        )
-                        .unwrap();
+                    let day = state.calendar().day() as u32;
+                    // the dot of the day is taken by PM of the hours next to it
+                    if self.marks_pm() {
+                        self.clock_display
+                            .show_number(DisplayPart::SideDisplay1, day, true)
+                            .unwrap();
+                    } else {
+                        self.clock_display
+                            .show_ordinal(DisplayPart::SideDisplay1, day, true)
+                            .unwrap();
+                    }
                 }
                 ClockPart::Month => {
                     self.clock_display
@@ -145,11 +266,10 @@ impl ClockDisplayViewer {
                 }
                 ClockPart::Hours => {
                     self.clock_display
-                        .show_number_at(
+                        .show_hours_at(
                             ClockDisplay::get_part_offset(DisplayPart::MainDisplay),
-                            2,
-                            state.calendar().hours() as u32,
-                            true,
+                            state.calendar().hours(),
+                            self.hour_format,
                         )
                         .unwrap();
                 }
@@ -172,32 +292,114 @@ impl ClockDisplayViewer {
                         )
                         .unwrap();
                 }
+                ClockPart::Weekday => {
+                    // centered on the main display, the outer digits are hidden
+                    let offset = ClockDisplay::get_part_offset(DisplayPart::MainDisplay);
+                    self.clock_display.hide(DisplayPart::MainDisplay);
+                    self.clock_display
+                        .show_text_at(offset + 1, 2, state.calendar().weekday().abbreviation())
+                        .unwrap();
+                }
+                ClockPart::MoonPhase => {
+                    // days into the lunation, illumination percentage and the glyph
+                    let moon = MoonPhase::from_calendar(state.calendar());
+                    self.clock_display
+                        .show_number(DisplayPart::SideDisplay1, moon.days() as u32, false)
+                        .unwrap();
+                    self.clock_display
+                        .show_number(DisplayPart::MainDisplay, moon.illumination() as u32, false)
+                        .unwrap();
+                    self.clock_display
+                        .show_segments(DisplayPart::SideDisplay2, &moon.phase().glyph())
+                        .unwrap();
+                }
+                ClockPart::DaysUntil => {
+                    // days and hours till the target date,
+                    // or since it, marked by a dash, once it has passed
+                    let Some(target) = state.target_date() else {
+                        self.clock_display.hide(DisplayPart::Whole);
+                        self.clock_display.show_text(DisplayPart::MainDisplay, "----").unwrap();
+                        continue;
+                    };
+
+                    let seconds = target.seconds_since(state.calendar());
+                    let hours = seconds.unsigned_abs() / 60 / 60;
+                    let days = (hours / 24).min(9999);
+
+                    if seconds >= 0 {
+                        self.clock_display.hide(DisplayPart::SideDisplay1);
+                    } else {
+                        self.clock_display.show_text(DisplayPart::SideDisplay1, " -").unwrap();
+                    }
+                    self.clock_display
+                        .show_number(DisplayPart::MainDisplay, days as u32, false)
+                        .unwrap();
+                    self.clock_display
+                        .show_number(DisplayPart::SideDisplay2, (hours % 24) as u32, false)
+                        .unwrap();
+                }
+                ClockPart::WorldHours => {
+                    // always in 24-hour format, the dot next to them
+                    // marks PM of the local hours
+                    if let Some(world) = state.world_calendar() {
+                        self.clock_display
+                            .show_number(DisplayPart::SideDisplay1, world.hours() as u32, true)
+                            .unwrap();
+                    }
+                }
+                ClockPart::WorldMinutes => {
+                    if let Some(world) = state.world_calendar() {
+                        self.clock_display
+                            .show_number(DisplayPart::SideDisplay2, world.minutes() as u32, true)
+                            .unwrap();
+                    }
+                }
             }
         }
 
-        if !self.parts[ClockPart::Day as usize] {
+        let whole_filled = self.parts[ClockPart::MoonPhase as usize] || self.parts[ClockPart::DaysUntil as usize];
+        let world = state.world_calendar().is_some();
+        let world_hours = world && self.parts[ClockPart::WorldHours as usize];
+        let world_minutes = world && self.parts[ClockPart::WorldMinutes as usize];
+        if !self.parts[ClockPart::Day as usize] && !whole_filled && !world_hours {
             self.clock_display.hide(DisplayPart::SideDisplay1);
         }
 
-        if !self.parts[ClockPart::Month as usize] && !self.parts[ClockPart::Seconds as usize] {
+        if !self.parts[ClockPart::Month as usize] && !self.parts[ClockPart::Seconds as usize] && !whole_filled && !world_minutes {
             self.clock_display.hide(DisplayPart::SideDisplay2);
         }
 
-        if !self.parts[ClockPart::Hours as usize] && !self.parts[ClockPart::Year as usize] {
+        let main_filled = self.parts[ClockPart::Year as usize] || self.parts[ClockPart::Weekday as usize] || whole_filled;
+        if !self.parts[ClockPart::Hours as usize] && !main_filled {
             self.clock_display.hide_at(ClockDisplay::get_part_offset(DisplayPart::MainDisplay), 2);
         }
 
-        if !self.parts[ClockPart::Minutes as usize] && !self.parts[ClockPart::Year as usize] {
+        if !self.parts[ClockPart::Minutes as usize] && !main_filled {
             self.clock_display.hide_at(ClockDisplay::get_part_offset(DisplayPart::MainDisplay) + 2, 2);
         }
 
+        // the first side display might have been shown, or hidden, over the dot
+        if self.marks_pm() {
+            let (_, pm) = self.hour_format.hours(state.calendar().hours());
+            self.clock_display.set_pm(pm);
+        }
+
         if self.parts[ClockPart::Hours as usize] && self.parts[ClockPart::Minutes as usize] {
             self.clock_display
-                .set_colon(state.calendar().seconds() % 2 == 0);
+                .set_colon(state.now().millis() < COLON_ON_MILLIS);
         } else {
             self.clock_display.set_colon(false);
         }
 
+        // the marquee is shown over the parts, until it's finished
+        if let Some(marquee) = self.marquee.as_mut().filter(|marquee| !marquee.finished()) {
+            if matches!(marquee.part(), DisplayPart::Whole | DisplayPart::MainDisplay) {
+                self.clock_display.set_colon(false);
+            }
+
+            marquee.update(&mut self.clock_display, state.timer().now());
+        }
+
         Ok(())
     }
 }
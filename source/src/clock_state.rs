@@ -1,19 +1,51 @@
-use stm32f1xx_hal::time::MonoTimer;
-
-use crate::calendar::Calendar;
+use crate::{
+    alarm::Alarms, calendar::Calendar, countdown_timer::CountdownTimer, mono_timer::MonoTimer,
+    rtc_calibration::Calibration, rtc_time::RtcTime, stopwatch::Stopwatch, time_zone::TimeZone,
+};
 
 pub struct ClockState {
     calendar: Calendar,
     timer: MonoTimer,
+    now: RtcTime,
+    stopwatch: Stopwatch,
+    countdown: CountdownTimer,
+    alarms: Alarms,
+    calibration: Calibration,
+    target_date: Option<Calendar>,
+    world_calendar: Option<Calendar>,
 }
 
 impl ClockState {
     pub fn new(calendar: Calendar, timer: MonoTimer) -> Self {
-        Self { calendar, timer }
+        let now = RtcTime::new(calendar.estimated_ticks(), 0);
+
+        Self {
+            calendar,
+            timer,
+            now,
+            stopwatch: Stopwatch::new(),
+            countdown: CountdownTimer::new(),
+            alarms: Alarms::new(),
+            calibration: Calibration::default(),
+            target_date: None,
+            world_calendar: None,
+        }
     }
 
-    pub fn timer(&self) -> MonoTimer {
-        self.timer
+    /// Monotonic timer, extended every RTC second.
+    pub fn timer(&self) -> &MonoTimer {
+        &self.timer
+    }
+
+    /// Time of the RTC as of the last update.
+    /// The milliseconds are kept consistent with the calendar,
+    /// they don't start over until the calendar counts the second.
+    pub fn now(&self) -> RtcTime {
+        if self.now.ticks() == self.calendar.estimated_ticks() {
+            self.now
+        } else {
+            RtcTime::new(self.calendar.estimated_ticks(), 999)
+        }
     }
 
     pub fn calendar(&self) -> &Calendar {
@@ -24,7 +56,97 @@ impl ClockState {
         &mut self.calendar
     }
 
+    pub fn stopwatch(&self) -> &Stopwatch {
+        &self.stopwatch
+    }
+
+    pub fn mut_stopwatch(&mut self) -> &mut Stopwatch {
+        &mut self.stopwatch
+    }
+
+    pub fn countdown(&self) -> &CountdownTimer {
+        &self.countdown
+    }
+
+    pub fn mut_countdown(&mut self) -> &mut CountdownTimer {
+        &mut self.countdown
+    }
+
+    pub fn alarms(&self) -> &Alarms {
+        &self.alarms
+    }
+
+    pub fn mut_alarms(&mut self) -> &mut Alarms {
+        &mut self.alarms
+    }
+
+    /// Correction of the RTC drift. Changes have to be applied
+    /// to the RTC and saved to the backup domain by the caller.
+    pub fn calibration(&self) -> &Calibration {
+        &self.calibration
+    }
+
+    pub fn mut_calibration(&mut self) -> &mut Calibration {
+        &mut self.calibration
+    }
+
+    /// Date the days are counted to, or since,
+    /// in the days until view.
+    pub fn target_date(&self) -> Option<&Calendar> {
+        self.target_date.as_ref()
+    }
+
+    pub fn set_target_date(&mut self, target_date: Option<Calendar>) {
+        self.target_date = target_date;
+    }
+
+    /// The calendar in the time zone of the world clock,
+    /// None if there is no world clock.
+    pub fn world_calendar(&self) -> Option<&Calendar> {
+        self.world_calendar.as_ref()
+    }
+
+    /// Sets the time zone of the world clock,
+    /// shown alongside the local time.
+    pub fn set_world_time_zone(&mut self, time_zone: Option<TimeZone>) {
+        self.world_calendar = time_zone.map(|time_zone| {
+            let mut world_calendar = self.calendar.clone();
+            world_calendar.set_time_zone(time_zone);
+            world_calendar
+        });
+    }
+
     pub fn second_elapsed(&mut self) {
-        self.calendar.second_elapsed()
+        self.timer.tick();
+        self.calendar.second_elapsed();
+        self.countdown.second_elapsed();
+
+        if let Some(world_calendar) = &mut self.world_calendar {
+            world_calendar.second_elapsed();
+
+            // both count the same UTC ticks, unless the calendar was changed
+            if world_calendar.estimated_ticks() != self.calendar.estimated_ticks() {
+                let time_zone = world_calendar.time_zone();
+                self.set_world_time_zone(Some(time_zone));
+            }
+        }
+    }
+
+    /// Updates the state from the current time of the RTC.
+    /// Should be called often, from the display timer.
+    ///
+    /// The calendar is synchronized with the RTC if it got off,
+    /// ie. after the time was set, or if the seconds interrupts were missed.
+    /// It may be a second behind while the seconds interrupt is pending.
+    pub fn update(&mut self, now: RtcTime) {
+        self.now = now;
+
+        let behind = now.ticks() as i64 - self.calendar.estimated_ticks() as i64;
+        if behind != 0 && behind != 1 {
+            self.calendar.add_seconds(behind);
+            if let Some(world_calendar) = &mut self.world_calendar {
+                world_calendar.add_seconds(behind);
+            }
+        }
     }
 }
@@ -0,0 +1,97 @@
+/// Longest duration that fits on the display, 99:59:59.
+pub const MAX_DURATION: u32 = 99 * 60 * 60 + 59 * 60 + 59;
+
+/// Timer counting down whole seconds,
+/// driven by the RTC second interrupt.
+/// After reaching zero, it stays expired until acknowledged.
+pub struct CountdownTimer {
+    duration: u32,
+    remaining: u32,
+    running: bool,
+    expired: bool,
+}
+
+impl CountdownTimer {
+    pub fn new() -> Self {
+        Self {
+            duration: 0,
+            remaining: 0,
+            running: false,
+            expired: false,
+        }
+    }
+
+    /// Duration the timer is set to, in seconds.
+    pub fn duration(&self) -> u32 {
+        self.duration
+    }
+
+    /// Sets the duration, stopping the timer.
+    /// Gets clamped to [MAX_DURATION](`MAX_DURATION`).
+    pub fn set_duration(&mut self, duration: u32) {
+        self.duration = duration.min(MAX_DURATION);
+        self.remaining = self.duration;
+        self.running = false;
+    }
+
+    /// Remaining seconds.
+    pub fn remaining(&self) -> u32 {
+        self.remaining
+    }
+
+    pub fn running(&self) -> bool {
+        self.running
+    }
+
+    pub fn expired(&self) -> bool {
+        self.expired
+    }
+
+    /// Starts or resumes the countdown.
+    /// If the timer has already run out, it's started
+    /// from the full duration again.
+    pub fn start(&mut self) {
+        if self.remaining == 0 {
+            self.remaining = self.duration;
+        }
+
+        self.running = self.remaining > 0;
+    }
+
+    pub fn pause(&mut self) {
+        self.running = false;
+    }
+
+    pub fn toggle(&mut self) {
+        if self.running {
+            self.pause();
+        } else {
+            self.start();
+        }
+    }
+
+    /// Stops the expiry alert and prepares
+    /// the timer for another run of the same duration.
+    pub fn acknowledge(&mut self) {
+        self.expired = false;
+        self.remaining = self.duration;
+    }
+
+    pub fn second_elapsed(&mut self) {
+        if !self.running {
+            return;
+        }
+
+        self.remaining = self.remaining.saturating_sub(1);
+        if self.remaining == 0 {
+            self.running = false;
+            self.expired = true;
+        }
+    }
+}
+
+impl Default for CountdownTimer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -9,6 +9,7 @@ use embedded_alloc::Heap;
 
 use panic_probe as _;
 
+pub mod alarm;
 pub mod brightness_manager;
 pub mod button;
 pub mod calendar;
@@ -17,11 +18,22 @@ pub mod clock_display;
 pub mod clock_display_viewer;
 pub mod clock_state;
 pub mod count_down;
+pub mod countdown_timer;
 pub mod display;
 pub mod linear_interpolation;
+pub mod marquee;
 pub mod mono_timer;
+pub mod moon;
 pub mod number_digits;
+pub mod number_format;
+pub mod rtc_calibration;
+pub mod rtc_epoch;
+pub mod rtc_time;
 pub mod seven_segments;
+pub mod stopwatch;
+pub mod sun;
+pub mod time_zone;
+pub mod transition;
 pub mod app_mode;
 
 extern crate alloc;
@@ -47,7 +59,21 @@ pub fn exit() -> ! {
 #[defmt_test::tests]
 mod unit_tests {
     use crate::{
-        calendar::Calendar,
+        alarm::{Alarm, Alarms},
+        brightness_manager::LOWEST_DISPLAY_BRIGHTNESS,
+        calendar::{Calendar, Weekday},
+        clock_app::RtcFlags,
+        clock_display::{ClockDisplay, DisplayPart, HourFormat},
+        marquee::Marquee,
+        mono_timer::{Duration, Instant},
+        number_format::{Alignment, NumberFormat, Radix},
+        rtc_calibration::{Calibration, MIN_MEASURED_PERIOD},
+        rtc_epoch::Epoch,
+        rtc_time::RtcTime,
+        seven_segments::{SevenSegments, UNRENDERABLE},
+        stopwatch::Stopwatch,
+        time_zone::{DstRule, TimeZone},
+        transition::{Transition, TransitionKind},
         linear_interpolation::{LinearInterpolation, Point},
     };
     use defmt::assert_eq;
@@ -140,4 +166,366 @@ mod unit_tests {
             315360000
         );
     }
+
+    #[test]
+    fn calendar_weekday() {
+        assert_eq!(Calendar::new(0, 0, 0, 1, 1, 2023).weekday(), Weekday::Sunday);
+        assert_eq!(Calendar::new(0, 0, 0, 2, 1, 2023).weekday(), Weekday::Monday);
+        assert_eq!(Calendar::new(0, 0, 0, 28, 2, 2024).weekday(), Weekday::Wednesday);
+        assert_eq!(Calendar::new(0, 0, 0, 29, 2, 2024).weekday(), Weekday::Thursday);
+        assert_eq!(Calendar::new(0, 0, 0, 1, 3, 2024).weekday(), Weekday::Friday);
+        assert_eq!(Calendar::new(23, 59, 59, 31, 12, 2099).weekday(), Weekday::Thursday);
+        assert_eq!(Calendar::new(0, 0, 0, 1, 1, 2100).weekday(), Weekday::Friday);
+    }
+
+    #[test]
+    fn alarm_after_dst_transition() {
+        let mut calendar = Calendar::from_ticks(2024, (89 * 24 + 12) * 60 * 60);
+        calendar.set_time_zone(TimeZone::new(60, DstRule::EuropeanUnion));
+        let ticks = calendar.to_ticks();
+
+        // 31. 3. 2024 7:00, in summer time
+        let alarm = Alarm::new(7, 0, true);
+        assert_eq!(alarm.next_ticks(ticks, &calendar), Some((90 * 24 + 5) * 60 * 60));
+        // 30. 3. 2024 15:00, in winter time
+        let alarm = Alarm::new(15, 0, true);
+        assert_eq!(alarm.next_ticks(ticks, &calendar), Some((89 * 24 + 14) * 60 * 60));
+    }
+
+    #[test]
+    fn alarm_on_weekday() {
+        // Sunday 1. 1. 2023
+        let calendar = Calendar::from_ticks(2023, 0);
+        let mut alarm = Alarm::new(7, 0, true);
+        alarm.set_days(0);
+        alarm.set_on_day(Weekday::Monday, true);
+
+        let mut alarms = Alarms::new();
+        alarms.set_alarm(0, alarm);
+        let monday = (24 + 7) * 60 * 60;
+        assert_eq!(alarms.next_alarm_ticks(0, &calendar), Some(monday));
+        assert!(alarms.due(monday, &calendar));
+    }
+
+    #[test]
+    fn alarm_skips_weekday() {
+        // Sunday 1. 1. 2023
+        let calendar = Calendar::from_ticks(2023, 0);
+        let mut alarm = Alarm::new(7, 0, true);
+        alarm.set_on_day(Weekday::Monday, false);
+
+        let mut alarms = Alarms::new();
+        alarms.set_alarm(0, alarm);
+        let sunday = 7 * 60 * 60;
+        assert_eq!(alarms.next_alarm_ticks(sunday, &calendar), Some(sunday + 2 * 24 * 60 * 60));
+        assert!(!alarms.due(sunday + 24 * 60 * 60, &calendar));
+        assert!(alarms.due(sunday + 2 * 24 * 60 * 60, &calendar));
+
+        alarm.set_days(0);
+        alarms.set_alarm(0, alarm);
+        assert_eq!(alarms.next_alarm_ticks(0, &calendar), None);
+    }
+
+    #[test]
+    fn alarm_is_not_a_second() {
+        let mut calendar = Calendar::from_ticks(2023, 7 * 60 * 60);
+
+        // the alarm raises the RTC interrupt as well, RSF and RTOFF are set too
+        let flags = RtcFlags::from_bits(0b10_1010);
+        assert!(flags.alarm());
+        if flags.second() {
+            calendar.second_elapsed();
+        }
+        assert_eq!(calendar.estimated_ticks(), 7 * 60 * 60);
+
+        let flags = RtcFlags::from_bits(0b10_1011);
+        assert!(flags.alarm());
+        assert!(flags.second());
+    }
+
+    #[test]
+    fn alarm_snoozed_at_last_ticks() {
+        let calendar = Calendar::from_ticks(2023, 0);
+        let mut alarms = Alarms::new();
+        alarms.ring();
+        alarms.snooze(u32::MAX - 60);
+        assert_eq!(alarms.next_alarm_ticks(u32::MAX - 60, &calendar), Some(u32::MAX));
+    }
+
+    #[test]
+    fn epoch_migration() {
+        let legacy = Epoch::new(2023);
+        let epoch = Epoch::new(2024);
+        let ticks = Calendar::with_base_year(2023, 12, 0, 0, 1, 6, 2024).to_ticks();
+
+        let migrated = legacy.migrate_ticks(ticks, epoch);
+        assert_eq!(migrated, Calendar::with_base_year(2024, 12, 0, 0, 1, 6, 2024).to_ticks());
+        assert_eq!(epoch.migrate_ticks(migrated, legacy), ticks);
+
+        // before the new epoch
+        assert_eq!(legacy.migrate_ticks(60, epoch), 0);
+
+        // legacy counter in local time, summer time of the time zone
+        let time_zone = TimeZone::new(60, DstRule::EuropeanUnion);
+        assert_eq!(
+            legacy.migrate_local_ticks(ticks, epoch, time_zone),
+            Calendar::with_base_year(2024, 10, 0, 0, 1, 6, 2024).to_ticks()
+        );
+    }
+
+    #[test]
+    fn twelve_hour_format() {
+        assert_eq!(HourFormat::TwelveHour.hours(0), (12, false));
+        assert_eq!(HourFormat::TwelveHour.hours(1), (1, false));
+        assert_eq!(HourFormat::TwelveHour.hours(11), (11, false));
+        assert_eq!(HourFormat::TwelveHour.hours(12), (12, true));
+        assert_eq!(HourFormat::TwelveHour.hours(13), (1, true));
+        assert_eq!(HourFormat::TwelveHour.hours(23), (11, true));
+        assert_eq!(HourFormat::TwentyFourHour.hours(0), (0, false));
+        assert_eq!(HourFormat::TwentyFourHour.hours(23), (23, false));
+    }
+
+    #[test]
+    fn rtc_time_millis() {
+        assert_eq!(RtcTime::new(5, 1500), RtcTime::new(6, 500));
+        assert_eq!(RtcTime::new(6, 500).millis_since(&RtcTime::new(5, 750)), 750);
+        assert_eq!(RtcTime::new(5, 750).millis_since(&RtcTime::new(6, 500)), -750);
+    }
+
+    #[test]
+    fn stopwatch_sub_second() {
+        let frequency = 24_000_000;
+        let at = |seconds: u64, millis: u64| {
+            Instant::from_cycles((seconds * 1000 + millis) * frequency as u64 / 1000, frequency)
+        };
+
+        let mut stopwatch = Stopwatch::new();
+        stopwatch.start(at(10, 900));
+        assert_eq!(stopwatch.elapsed(at(11, 50)), 15);
+
+        stopwatch.stop(at(12, 400));
+        assert_eq!(stopwatch.elapsed(at(20, 0)), 150);
+
+        stopwatch.start(at(30, 0));
+        stopwatch.lap(at(30, 999));
+        assert_eq!(stopwatch.split(0), Some(249));
+    }
+
+    #[test]
+    fn stopwatch_dropped_laps() {
+        let at = |seconds: u64| Instant::from_cycles(seconds * 24_000_000, 24_000_000);
+        let mut stopwatch = Stopwatch::new();
+        stopwatch.start(at(0));
+        for lap in 1..=12 {
+            stopwatch.lap(at(lap * lap));
+        }
+
+        // the first two laps are dropped, the third one is timed from the second
+        assert_eq!(stopwatch.laps_count(), 10);
+        assert_eq!(stopwatch.split(0), Some(900));
+        assert_eq!(stopwatch.lap_time(0), Some(500));
+        assert_eq!(stopwatch.lap_time(9), Some(2300));
+
+        stopwatch.reset();
+        stopwatch.start(at(200));
+        stopwatch.lap(at(201));
+        assert_eq!(stopwatch.lap_time(0), Some(100));
+    }
+
+    #[test]
+    fn calibration_registers() {
+        assert_eq!(Calibration::new(0).registers(), (32767, 0));
+        assert_eq!(Calibration::new(10).registers(), (32767, 10));
+        assert_eq!(Calibration::new(121).registers(), (32767, 127));
+        assert_eq!(Calibration::new(-10).registers(), (32766, 22));
+        assert_eq!(Calibration::new(-30).registers(), (32766, 1));
+    }
+
+    #[test]
+    fn calibration_measured_drift() {
+        let mut calibration = Calibration::new(2);
+
+        // the first correction only starts the measurement
+        assert!(!calibration.time_set(1000, 1000));
+        assert_eq!(calibration.last_set(), Some(1000));
+
+        // too early to measure
+        assert!(!calibration.time_set(2000, 1999));
+        assert_eq!(calibration.ppm(), 2);
+
+        // 6 seconds fast in two weeks are 5 ppm
+        let last_set = 1999 + 2 * MIN_MEASURED_PERIOD;
+        assert!(calibration.time_set(last_set + 6, last_set));
+        assert_eq!(calibration.ppm(), 7);
+
+        // the date changed, the measurement moves along
+        calibration.time_shifted(24 * 60 * 60);
+        assert_eq!(calibration.last_set(), Some(last_set + 24 * 60 * 60));
+    }
+
+    #[test]
+    fn mono_timer_duration() {
+        let frequency = 24_000_000;
+        let duration = Duration::from_cycles(frequency as u64 * 3 + 12_000, frequency);
+        assert_eq!(duration.as_secs(), 3);
+        assert_eq!(duration.as_millis(), 3_000);
+        assert_eq!(duration.as_micros(), 3_000_500);
+
+        // longer than the cycle counter overflows, ~179 s
+        let day = Duration::from_cycles(frequency as u64 * 24 * 60 * 60, frequency);
+        assert_eq!(day.as_micros(), 24 * 60 * 60 * 1_000_000);
+    }
+
+    #[test]
+    fn seven_segment_font() {
+        for digit in 0..10 {
+            let character = char::from_digit(digit, 10).unwrap();
+            assert_eq!(
+                SevenSegments::letter_to_segments(character),
+                SevenSegments::digit_to_segments(digit as u8)
+            );
+        }
+
+        for character in SevenSegments::supported() {
+            assert!(SevenSegments::letter_to_segments(character) != UNRENDERABLE);
+        }
+
+        assert!(SevenSegments::is_supported('°'));
+        assert_eq!(SevenSegments::letter_to_segments('-'), 0b0000001 << 1);
+        assert_eq!(SevenSegments::char_to_segments('~'), None);
+        assert_eq!(SevenSegments::letter_to_segments('~'), UNRENDERABLE);
+    }
+
+    #[test]
+    fn marquee_frames() {
+        let marquee = Marquee::new("Hello", DisplayPart::SideDisplay2, 100);
+        let h = SevenSegments::letter_to_segments('H');
+        let e = SevenSegments::letter_to_segments('e');
+        let o = SevenSegments::letter_to_segments('o');
+
+        assert_eq!(marquee.steps(), 7);
+        assert_eq!(marquee.frame(0)[..2], [0, 0]);
+        assert_eq!(marquee.frame(1)[..2], [0, h]);
+        assert_eq!(marquee.frame(2)[..2], [h, e]);
+        assert_eq!(marquee.frame(6)[..2], [o, 0]);
+        assert_eq!(marquee.frame(7)[..2], [0, 0]);
+        assert!(!marquee.finished());
+    }
+
+    #[test]
+    fn text_dot_folding() {
+        let digit = |digit| SevenSegments::digit_to_segments(digit);
+
+        // second side display, both digits have a dot
+        let mut segments = [0; 2];
+        ClockDisplay::text_segments(6, "1.2.", &mut segments).unwrap();
+        assert_eq!(segments, [digit(1) | 1, digit(2) | 1]);
+
+        // main display, the colon is on the middle digits
+        let mut segments = [0; 4];
+        ClockDisplay::text_segments(2, "12:34", &mut segments).unwrap();
+        assert_eq!(segments, [digit(1), digit(2) | 1, digit(3) | 1, digit(4)]);
+
+        let mut segments = [0; 4];
+        ClockDisplay::text_segments(2, "12.5", &mut segments).unwrap();
+        assert_eq!(segments, [digit(1), digit(2) | 1, digit(5), 0]);
+
+        // the first digit of the main display has no dot
+        let mut segments = [0; 4];
+        ClockDisplay::text_segments(2, "1.5", &mut segments).unwrap();
+        assert_eq!(segments, [digit(1), 1, digit(5), 0]);
+
+        // dots that cannot be merged take a digit
+        let mut segments = [0; 2];
+        ClockDisplay::text_segments(0, ".5", &mut segments).unwrap();
+        assert_eq!(segments, [1, digit(5)]);
+
+        let mut segments = [0; 2];
+        ClockDisplay::text_segments(0, "1..", &mut segments).unwrap();
+        assert_eq!(segments, [digit(1) | 1, 1]);
+
+        assert!(ClockDisplay::text_segments(6, "1.2.3", &mut [0; 2]).is_err());
+    }
+
+    #[test]
+    fn number_formatting() {
+        let digit = |digit| SevenSegments::digit_to_segments(digit);
+        let letter = |letter| SevenSegments::letter_to_segments(letter);
+
+        let mut segments = [0; 4];
+        assert!(NumberFormat::new().segments(2, -30, &mut segments));
+        assert_eq!(segments, [0, letter('-'), digit(3), digit(0)]);
+
+        // the dot is merged into the colon digit
+        let mut segments = [0; 4];
+        assert!(NumberFormat::new().decimals(2).segments(2, 125, &mut segments));
+        assert_eq!(segments, [0, digit(1) | 1, digit(2), digit(5)]);
+
+        let mut segments = [0; 2];
+        assert!(NumberFormat::new().decimals(1).segments(6, 5, &mut segments));
+        assert_eq!(segments, [digit(0) | 1, digit(5)]);
+
+        let mut segments = [0; 2];
+        assert!(NumberFormat::new().min_digits(2).segments(0, 5, &mut segments));
+        assert_eq!(segments, [digit(0), digit(5)]);
+
+        let mut segments = [0; 4];
+        let format = NumberFormat::new()
+            .radix(Radix::Hexadecimal)
+            .align(Alignment::Left);
+        assert!(format.segments(2, 0xAF, &mut segments));
+        assert_eq!(segments, [letter('A'), letter('F'), 0, 0]);
+
+        let mut segments = [0; 4];
+        let format = NumberFormat::new().align(Alignment::Center);
+        assert!(format.segments(2, 7, &mut segments));
+        assert_eq!(segments, [0, digit(7), 0, 0]);
+
+        let mut segments = [0; 4];
+        assert!(!NumberFormat::new().segments(2, -1234, &mut segments));
+        assert_eq!(segments, [UNRENDERABLE; 4]);
+    }
+
+    #[test]
+    fn transition_frames() {
+        let from = [1, 2, 3, 4, 5, 6, 7, 8];
+        let to = [11, 12, 13, 14, 15, 16, 17, 18];
+        let transition = |kind| {
+            let mut transition = Transition::new(kind, from, 100);
+            transition.set_target(to);
+            transition
+        };
+
+        let slide = transition(TransitionKind::SlideLeft);
+        assert_eq!(slide.frame(0), from);
+        assert_eq!(slide.frame(250), [3, 4, 5, 6, 7, 8, 11, 12]);
+        assert_eq!(slide.frame(1000), to);
+
+        let slide = transition(TransitionKind::SlideRight);
+        assert_eq!(slide.frame(250), [17, 18, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(slide.frame(1000), to);
+
+        // a dash rolls up to the top bar, the top bar comes to the middle
+        let dash = SevenSegments::letter_to_segments('-');
+        let top = SevenSegments::digit_to_segments(7) & !SevenSegments::digit_to_segments(1);
+        let mut roll = Transition::new(TransitionKind::Roll, [dash, 5, 0, 0, 0, 0, 0, 0], 100);
+        roll.set_target([top, 5, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(roll.frame(0)[..2], [dash, 5]);
+        assert_eq!(roll.frame(500)[..2], [top | dash, 5]);
+        assert_eq!(roll.frame(1000)[..2], [top, 5]);
+
+        let fade = transition(TransitionKind::Fade);
+        assert_eq!(fade.frame(400), from);
+        assert_eq!(fade.frame(600), to);
+        assert_eq!(Transition::fade_level(0), 1000);
+        assert_eq!(Transition::fade_level(250), 500);
+        assert_eq!(Transition::fade_level(500), 0);
+        assert_eq!(Transition::fade_level(1000), 1000);
+
+        let lowest = LOWEST_DISPLAY_BRIGHTNESS;
+        assert_eq!(Transition::faded(lowest + 1000, 1000), lowest + 1000);
+        assert_eq!(Transition::faded(lowest + 1000, 500), lowest + 500);
+        assert_eq!(Transition::faded(lowest + 1000, 0), lowest);
+        assert_eq!(Transition::faded(0, 500), 0);
+    }
 }
@@ -6,6 +6,7 @@
 
 extern crate alloc;
 
+pub mod alarm;
 pub mod brightness_manager;
 pub mod button;
 pub mod calendar;
@@ -14,18 +15,29 @@ pub mod clock_display;
 pub mod clock_display_viewer;
 pub mod clock_state;
 pub mod count_down;
+pub mod countdown_timer;
 pub mod display;
 pub mod linear_interpolation;
+pub mod marquee;
 pub mod mono_timer;
+pub mod moon;
 pub mod number_digits;
+pub mod number_format;
+pub mod rtc_calibration;
+pub mod rtc_epoch;
+pub mod rtc_time;
 pub mod seven_segments;
+pub mod stopwatch;
+pub mod sun;
+pub mod time_zone;
+pub mod transition;
 pub mod app_mode;
 
 use alloc::boxed::Box;
 use button::{ActiveHigh, Button, ButtonState};
 use calendar::Calendar;
 use clock_app::{ClockApp, ClockInterrupt};
-use clock_display::{ClockDisplay, DisplayPart};
+use clock_display::{ClockDisplay, DisplayPart, HourFormat};
 use clock_display_viewer::{ClockDisplayViewer, DisplayView};
 use clock_state::ClockState;
 use core::{alloc::Layout, cell::RefCell, convert::Infallible, panic::PanicInfo};
@@ -37,6 +49,7 @@ use display::Display;
 use embedded_alloc::Heap;
 use embedded_hal::digital::v2::OutputPin;
 use fugit::MicrosDurationU32;
+use mono_timer::MonoTimer;
 use stm32f1xx_hal::{
     afio::MAPR,
     gpio::{Cr, Floating, Input, Pin},
@@ -48,15 +61,40 @@ use stm32f1xx_hal::{
         RestoredOrNewRtc::{New, Restored},
         Rtc,
     },
-    time::MonoTimer,
     timer::{Event, SysDelay, Tim1NoRemap, Tim2NoRemap, Tim3NoRemap, TimerExt},
 };
+use rtc_calibration::Calibration;
+use rtc_epoch::Epoch;
+use sun::Location;
+use time_zone::{DstRule, TimeZone};
 
 use defmt_rtt as _;
 
 #[global_allocator]
 static HEAP: Heap = Heap::empty();
 
+/// The RTC counts UTC seconds since the start of the base year.
+/// It overflows 136 years after it, the base year may be moved
+/// forward, the counter is migrated to it upon the next start.
+const BASE_YEAR: u16 = 2023;
+
+/// The RTC counts UTC time, local time is shown.
+/// Central European Time is UTC+1 standard time.
+const TIME_ZONE: TimeZone = TimeZone::new(60, DstRule::EuropeanUnion);
+
+/// Location of the clock, sunrise and sunset
+/// there drive the automatic brightness.
+const LOCATION: Location = Location::new(50.08, 14.42);
+
+/// Time zone of the world clock, shown alongside the local time.
+const WORLD_TIME_ZONE: TimeZone = TimeZone::new(-5 * 60, DstRule::UnitedStates);
+
+/// Date the days are counted to in the days until view, ISO 8601.
+const TARGET_DATE: &str = "2027-01-01T00:00:00+01:00";
+
+/// Initial format of the hours, until it's changed at runtime.
+const HOUR_FORMAT: HourFormat = HourFormat::TwentyFourHour;
+
 static APP: Mutex<RefCell<Option<ClockApp>>> = Mutex::new(RefCell::new(Option::None));
 
 #[interrupt]
@@ -69,6 +107,20 @@ fn RTC() {
     });
 }
 
+#[interrupt]
+fn RTCALARM() {
+    critical_section::with(|cs| {
+        let mut app = APP.borrow_ref_mut(cs);
+        let app = app.as_mut().unwrap();
+
+        app.interrupt(ClockInterrupt::Alarm);
+    });
+
+    // RTC alarm is connected to EXTI line 17,
+    // its pending bit has to be cleared as well.
+    unsafe { (*pac::EXTI::ptr()).pr.write(|w| w.pr17().set_bit()) };
+}
+
 #[interrupt]
 fn TIM4() {
     critical_section::with(|cs| {
@@ -295,12 +347,31 @@ fn main() -> ! {
     );
     display.set_current_view(DisplayView::ClockView);
 
-    let mut rtc = match Rtc::restore_or_new(dp.RTC, &mut backup_domain) {
-        Restored(rtc) => rtc,
-        New(rtc) => rtc,
+    let (mut rtc, new_rtc) = match Rtc::restore_or_new(dp.RTC, &mut backup_domain) {
+        Restored(rtc) => (rtc, false),
+        New(rtc) => (rtc, true),
     };
+    let epoch = Epoch::restore(
+        &mut rtc,
+        &mut backup_domain,
+        new_rtc,
+        Epoch::new(BASE_YEAR),
+        TIME_ZONE,
+    );
+    display.set_hour_format(HourFormat::load(&backup_domain).unwrap_or(HOUR_FORMAT));
+
+    // The correction of the drift is kept by the RTC, applying it again
+    // would restart the current second, only a new RTC has to be corrected.
+    let calibration = Calibration::load(&backup_domain);
+    if new_rtc {
+        calibration.apply(&mut rtc, &mut backup_domain);
+    }
     rtc.listen_seconds();
 
+    // RTC alarm interrupt goes through EXTI line 17
+    dp.EXTI.imr.modify(|_, w| w.mr17().set_bit());
+    dp.EXTI.rtsr.modify(|_, w| w.tr17().set_bit());
+
     // Initialize the state inside of a critical section,
     // to make sure that if a second will elapse during state initialization,
     // an interrupt will be called afterwards.
@@ -310,14 +381,19 @@ fn main() -> ! {
             // RTC interrupt cannot be called prior APP being Some,
             // otherwise a panic would result.
             cortex_m::peripheral::NVIC::unmask(interrupt::RTC);
+            cortex_m::peripheral::NVIC::unmask(interrupt::RTCALARM);
         }
 
-        let state = ClockState::new(
-            Calendar::from_ticks(2023, current_time),
-            MonoTimer::new(cp.DWT, cp.DCB, clocks),
-        );
+        let mut calendar = Calendar::from_ticks(epoch.base_year(), current_time);
+        calendar.set_time_zone(TIME_ZONE);
 
-        let app = ClockApp::new(rtc, display, state);
+        let mut state = ClockState::new(calendar, MonoTimer::new(cp.DWT, cp.DCB, clocks));
+        state.set_target_date(Calendar::parse_iso8601(epoch.base_year(), TARGET_DATE).ok());
+        state.set_world_time_zone(Some(WORLD_TIME_ZONE));
+        *state.mut_calibration() = calibration;
+
+        let mut app = ClockApp::new(rtc, backup_domain, display, state);
+        app.brightness().set_location(Some(LOCATION));
         APP.borrow(cs).replace(Some(app));
     });
 
@@ -335,10 +411,15 @@ fn main_loop(
     mut leds: [Box<dyn OutputPin<Error = Infallible> + Send>; 4],
 ) -> ! {
     loop {
+        let alert_blink = critical_section::with(|cs| {
+            let app = APP.borrow_ref(cs);
+            app.as_ref().unwrap().alert_blink()
+        });
+
         for (i, btn) in btns.iter_mut().enumerate() {
             btn.update();
 
-            if btn.is_pressed() {
+            if btn.is_pressed() || alert_blink {
                 leds[i].set_low().unwrap();
             } else {
                 leds[i].set_high().unwrap();
@@ -365,6 +446,7 @@ fn panic(info: &PanicInfo) -> ! {
     defmt::flush();
 
     cortex_m::peripheral::NVIC::mask(interrupt::RTC);
+    cortex_m::peripheral::NVIC::mask(interrupt::RTCALARM);
     critical_section::with(|cs| {
         let mut app = APP.borrow_ref_mut(cs);
         let app = app.as_mut().unwrap();
@@ -385,6 +467,7 @@ fn panic(info: &PanicInfo) -> ! {
 #[alloc_error_handler]
 fn oom(_: Layout) -> ! {
     cortex_m::peripheral::NVIC::mask(interrupt::RTC);
+    cortex_m::peripheral::NVIC::mask(interrupt::RTCALARM);
     critical_section::with(|cs| {
         let mut app = APP.borrow_ref_mut(cs);
         let app = app.as_mut().unwrap();
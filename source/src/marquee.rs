@@ -0,0 +1,118 @@
+use alloc::string::String;
+
+use crate::{
+    clock_display::{ClockDisplay, DisplayPart},
+    mono_timer::Instant,
+    seven_segments::SevenSegments,
+};
+
+/// Digits of the whole display, the most a marquee may span.
+const DIGITS: usize = 8;
+
+/// Time a character takes to move by one digit, in milliseconds,
+/// readable without being too slow.
+pub const DEFAULT_STEP_MILLIS: u32 = 300;
+
+/// Text scrolling from the right to the left across a part of the display,
+/// for texts that don't fit the part.
+///
+/// The text scrolls in from the right edge of the part
+/// and out of its left edge, then the marquee is finished.
+/// The marquee keeps its own copy of the text,
+/// so it may be made at runtime.
+/// It's driven by [update](`Marquee::update`), called from the display timer.
+pub struct Marquee {
+    text: String,
+    part: DisplayPart,
+    step_millis: u32,
+    position: usize,
+    started: Option<(Instant, usize)>,
+}
+
+impl Marquee {
+    /// Creates a marquee moving by a digit every step_millis.
+    pub fn new(text: &str, part: DisplayPart, step_millis: u32) -> Self {
+        Self {
+            text: String::from(text),
+            part,
+            step_millis: step_millis.max(1),
+            position: 0,
+            started: None,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn part(&self) -> DisplayPart {
+        self.part
+    }
+
+    pub fn step_millis(&self) -> u32 {
+        self.step_millis
+    }
+
+    /// Changes the speed, the text keeps its position.
+    pub fn set_step_millis(&mut self, step_millis: u32) {
+        self.step_millis = step_millis.max(1);
+        self.started = None;
+    }
+
+    /// Steps from the start, the text is out of the part
+    /// at the first and the last one.
+    pub fn steps(&self) -> usize {
+        self.text.chars().count() + ClockDisplay::get_part_size(self.part)
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn finished(&self) -> bool {
+        self.position >= self.steps()
+    }
+
+    /// Starts the scroll over.
+    pub fn restart(&mut self) {
+        self.position = 0;
+        self.started = None;
+    }
+
+    /// Moves the text by the steps elapsed since the last update
+    /// and shows it on the part. Nothing is shown once finished.
+    /// Returns whether the marquee is finished.
+    pub fn update(&mut self, display: &mut ClockDisplay, now: Instant) -> bool {
+        let (started, start_position) = *self.started.get_or_insert((now, self.position));
+        let steps = now.duration_since(started).as_millis() / self.step_millis as u64;
+        self.position = (start_position + steps as usize).min(self.steps());
+
+        if self.finished() {
+            return true;
+        }
+
+        let size = ClockDisplay::get_part_size(self.part);
+        display
+            .show_segments(self.part, &self.frame(self.position)[..size])
+            .unwrap();
+        false
+    }
+
+    /// Segments shown on the part at the given position,
+    /// only the first digits, as many as the part has, are used.
+    pub fn frame(&self, position: usize) -> [u8; DIGITS] {
+        let size = ClockDisplay::get_part_size(self.part);
+        let mut segments = [0; DIGITS];
+
+        // the text starts right after the last digit of the part
+        for (digit, segment) in segments.iter_mut().enumerate().take(size) {
+            if let Some(index) = (position + digit).checked_sub(size) {
+                if let Some(character) = self.text.chars().nth(index) {
+                    *segment = SevenSegments::letter_to_segments(character);
+                }
+            }
+        }
+
+        segments
+    }
+}
@@ -0,0 +1,160 @@
+use core::ops::{Add, Sub};
+
+use cortex_m::peripheral::{DCB, DWT};
+use fugit::HertzU32;
+use stm32f1xx_hal::rcc::Clocks;
+
+/// Monotonic timer counting the cycles of the core,
+/// using the DWT cycle counter.
+///
+/// The cycle counter overflows quite often (every ~179 s at 24 MHz),
+/// it's extended to 64 bits by [tick](`MonoTimer::tick`),
+/// that has to be called more often than that, ie. every RTC second.
+/// Instants may then be compared no matter how far apart they are.
+pub struct MonoTimer {
+    frequency: HertzU32,
+    /// Extended cycles at the last tick.
+    base: u64,
+    /// Value of the cycle counter at the last tick.
+    base_cycles: u32,
+}
+
+impl MonoTimer {
+    /// Enables the cycle counter.
+    pub fn new(mut dwt: DWT, mut dcb: DCB, clocks: Clocks) -> Self {
+        dcb.enable_trace();
+        dwt.enable_cycle_counter();
+
+        Self {
+            frequency: clocks.sysclk(),
+            base: 0,
+            base_cycles: DWT::cycle_count(),
+        }
+    }
+
+    /// Frequency of the cycles.
+    pub fn frequency(&self) -> HertzU32 {
+        self.frequency
+    }
+
+    /// Accounts for the cycles elapsed since the last tick.
+    /// Has to be called more often than the cycle counter overflows.
+    pub fn tick(&mut self) {
+        let cycles = DWT::cycle_count();
+
+        self.base += cycles.wrapping_sub(self.base_cycles) as u64;
+        self.base_cycles = cycles;
+    }
+
+    pub fn now(&self) -> Instant {
+        // less than an overflow elapsed since the last tick,
+        // the difference fits into the cycle counter
+        let elapsed = DWT::cycle_count().wrapping_sub(self.base_cycles);
+
+        Instant {
+            cycles: self.base + elapsed as u64,
+            frequency: self.frequency.raw(),
+        }
+    }
+}
+
+/// Instant of a [MonoTimer](`MonoTimer`), in cycles since its creation.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Instant {
+    cycles: u64,
+    frequency: u32,
+}
+
+impl Instant {
+    pub fn from_cycles(cycles: u64, frequency: u32) -> Self {
+        Self { cycles, frequency }
+    }
+
+    pub fn cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    /// Time elapsed since the earlier instant,
+    /// zero if it's not earlier.
+    pub fn duration_since(&self, earlier: Instant) -> Duration {
+        self.checked_duration_since(earlier)
+            .unwrap_or(Duration::from_cycles(0, self.frequency))
+    }
+
+    /// Time elapsed since the earlier instant,
+    /// None if it's not earlier.
+    pub fn checked_duration_since(&self, earlier: Instant) -> Option<Duration> {
+        let cycles = self.cycles.checked_sub(earlier.cycles)?;
+        Some(Duration::from_cycles(cycles, self.frequency))
+    }
+
+    /// Time elapsed since this instant, as measured by the timer.
+    pub fn elapsed(&self, timer: &MonoTimer) -> Duration {
+        timer.now().duration_since(*self)
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    fn add(self, duration: Duration) -> Instant {
+        Instant {
+            cycles: self.cycles + duration.cycles,
+            frequency: self.frequency,
+        }
+    }
+}
+
+impl Sub for Instant {
+    type Output = Duration;
+
+    fn sub(self, earlier: Instant) -> Duration {
+        self.duration_since(earlier)
+    }
+}
+
+/// Span of time between two [Instants](`Instant`),
+/// in cycles of the timer.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Duration {
+    cycles: u64,
+    frequency: u32,
+}
+
+impl Duration {
+    pub fn from_cycles(cycles: u64, frequency: u32) -> Self {
+        Self { cycles, frequency }
+    }
+
+    pub fn cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    pub fn as_secs(&self) -> u64 {
+        self.cycles / self.frequency as u64
+    }
+
+    pub fn as_millis(&self) -> u64 {
+        self.scaled(1_000)
+    }
+
+    pub fn as_micros(&self) -> u64 {
+        self.scaled(1_000_000)
+    }
+
+    /// The duration in units of the given fraction of a second.
+    /// Whole seconds are scaled separately, so the cycles don't overflow.
+    fn scaled(&self, units_per_second: u64) -> u64 {
+        let frequency = self.frequency as u64;
+        self.cycles / frequency * units_per_second
+            + self.cycles % frequency * units_per_second / frequency
+    }
+}
+
+impl Add for Duration {
+    type Output = Duration;
+
+    fn add(self, other: Duration) -> Duration {
+        Duration::from_cycles(self.cycles + other.cycles, self.frequency)
+    }
+}
@@ -0,0 +1,91 @@
+use core::f32::consts::PI;
+
+use libm::cosf;
+
+use crate::calendar::Calendar;
+
+/// Mean length of the lunation, from new moon to new moon, in seconds.
+const SYNODIC_MONTH: i64 = 2551443;
+
+const SECONDS_IN_DAY: u32 = 24 * 60 * 60;
+
+// segments of the glyphs, a b c d e f g
+const TOP_AND_BOTTOM: u8 = 0b1001000 << 1;
+const LEFT_HALF: u8 = 0b1001110 << 1;
+const RIGHT_HALF: u8 = 0b1111000 << 1;
+const LEFT_EDGE: u8 = 0b0000110 << 1;
+const RIGHT_EDGE: u8 = 0b0110000 << 1;
+const MIDDLE: u8 = 0b0000001 << 1;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, defmt::Format)]
+pub enum Phase {
+    NewMoon,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    FullMoon,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+impl Phase {
+    /// Segments of two digits showing the lit part
+    /// of the moon, as seen from the northern hemisphere.
+    /// New moon is shown as two dashes.
+    pub fn glyph(&self) -> [u8; 2] {
+        match self {
+            Phase::NewMoon => [MIDDLE, MIDDLE],
+            Phase::WaxingCrescent => [0, RIGHT_EDGE],
+            Phase::FirstQuarter => [0, RIGHT_HALF],
+            Phase::WaxingGibbous => [TOP_AND_BOTTOM, RIGHT_HALF],
+            Phase::FullMoon => [LEFT_HALF, RIGHT_HALF],
+            Phase::WaningGibbous => [LEFT_HALF, TOP_AND_BOTTOM],
+            Phase::LastQuarter => [LEFT_HALF, 0],
+            Phase::WaningCrescent => [LEFT_EDGE, 0],
+        }
+    }
+}
+
+/// Phase of the moon, computed from the mean lunation.
+/// The real moon may be more than half a day off.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct MoonPhase {
+    /// Seconds since the last new moon.
+    age: u32,
+}
+
+impl MoonPhase {
+    pub fn from_calendar(calendar: &Calendar) -> Self {
+        // new moon of 6. 1. 2000 18:14 UTC
+        let reference = Calendar::new(18, 14, 0, 6, 1, 2000);
+
+        Self {
+            age: calendar.seconds_since(&reference).rem_euclid(SYNODIC_MONTH) as u32,
+        }
+    }
+
+    /// Whole days since the last new moon, 0 - 29.
+    pub fn days(&self) -> u8 {
+        (self.age / SECONDS_IN_DAY) as u8
+    }
+
+    /// Percentage of the moon disc that is lit.
+    pub fn illumination(&self) -> u8 {
+        let angle = 2.0 * PI * self.age as f32 / SYNODIC_MONTH as f32;
+        ((1.0 - cosf(angle)) * 50.0 + 0.5) as u8
+    }
+
+    pub fn phase(&self) -> Phase {
+        match (self.age as i64 * 8 + SYNODIC_MONTH / 2) / SYNODIC_MONTH % 8 {
+            0 => Phase::NewMoon,
+            1 => Phase::WaxingCrescent,
+            2 => Phase::FirstQuarter,
+            3 => Phase::WaxingGibbous,
+            4 => Phase::FullMoon,
+            5 => Phase::WaningGibbous,
+            6 => Phase::LastQuarter,
+            _ => Phase::WaningCrescent,
+        }
+    }
+}
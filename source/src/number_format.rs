@@ -0,0 +1,184 @@
+use crate::{clock_display::ClockDisplay, seven_segments::UNRENDERABLE};
+
+/// Digits of the whole display, the most a number may span.
+const DIGITS: usize = 8;
+
+/// Most characters of a formatted number,
+/// the sign, 20 digits of u64::MAX and the decimal point.
+const MAX_CHARACTERS: usize = 22;
+
+/// Most digits of a number, all of them may be decimals but one.
+const MAX_DIGITS: u8 = 20;
+
+const DIGIT_CHARACTERS: &[u8; 16] = b"0123456789ABCDEF";
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Radix {
+    Decimal,
+    /// Shown as 0-9, A b C d E F.
+    Hexadecimal,
+}
+
+impl Radix {
+    fn base(&self) -> u64 {
+        match self {
+            Radix::Decimal => 10,
+            Radix::Hexadecimal => 16,
+        }
+    }
+}
+
+/// Where a number shorter than the digits it's shown on is placed.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Alignment {
+    Left,
+    Right,
+    /// Nearer to the left if it cannot be centered exactly.
+    Center,
+}
+
+/// How a number is shown on the display, see
+/// [show_formatted](`ClockDisplay::show_formatted`).
+///
+/// Negative numbers are shown with a minus in front of them.
+/// Numbers with decimals are fixed point, the last digits are the decimals,
+/// separated by a dot, ie. 125 with two decimals is shown as 1.25.
+/// The dot is merged into the previous digit the same way
+/// as in [text_segments](`ClockDisplay::text_segments`).
+///
+/// Numbers that don't fit are shown as
+/// [UNRENDERABLE](`crate::seven_segments::UNRENDERABLE`) on every digit.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct NumberFormat {
+    radix: Radix,
+    decimals: u8,
+    min_digits: u8,
+    alignment: Alignment,
+}
+
+impl NumberFormat {
+    /// Decimal, right aligned, without decimals and leading zeros.
+    pub fn new() -> Self {
+        Self {
+            radix: Radix::Decimal,
+            decimals: 0,
+            min_digits: 1,
+            alignment: Alignment::Right,
+        }
+    }
+
+    pub fn radix(mut self, radix: Radix) -> Self {
+        self.radix = radix;
+        self
+    }
+
+    /// Count of the last digits that are decimals, there is always
+    /// a digit in front of the dot.
+    pub fn decimals(mut self, decimals: u8) -> Self {
+        self.decimals = decimals.min(MAX_DIGITS - 1);
+        self
+    }
+
+    /// Pads the number with leading zeros to the digits, not counting the sign.
+    pub fn min_digits(mut self, digits: u8) -> Self {
+        self.min_digits = digits.min(MAX_DIGITS);
+        self
+    }
+
+    pub fn align(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Converts the number to the segments of the digits starting at the offset,
+    /// aligned within them. The digits the number doesn't take are cleared.
+    ///
+    /// Returns whether the number fits, all the digits show
+    /// [UNRENDERABLE](`crate::seven_segments::UNRENDERABLE`) if it doesn't.
+    pub fn segments(&self, offset: usize, number: i64, segments: &mut [u8]) -> bool {
+        let text = self.text(number);
+        let text = text.as_str();
+        let size = segments.len();
+
+        // the digits the dot is merged into depend on the position,
+        // so the width is measured at the position it's shown at
+        let mut scratch = [0; DIGITS];
+        let mut width = |shift: usize| {
+            ClockDisplay::text_segments(offset + shift, text, &mut scratch[shift..size]).ok()
+        };
+
+        let shift = match self.alignment {
+            Alignment::Left => Some(0),
+            Alignment::Right => (0..size).rev().find(|&shift| width(shift).is_some()),
+            // the dot may only take a digit more when shifted,
+            // there is room for it unless the shift is zero
+            Alignment::Center => width(0).map(|width| (size - width) / 2),
+        };
+
+        segments.fill(0);
+        let fits = shift.is_some_and(|shift| {
+            ClockDisplay::text_segments(offset + shift, text, &mut segments[shift..]).is_ok()
+        });
+
+        if !fits {
+            segments.fill(UNRENDERABLE);
+        }
+        fits
+    }
+
+    fn text(&self, number: i64) -> NumberText {
+        let mut text = NumberText::new();
+        let base = self.radix.base();
+        let digits = self.min_digits.max(self.decimals + 1);
+
+        let mut magnitude = number.unsigned_abs();
+        let mut digit = 0;
+        while magnitude != 0 || digit < digits {
+            if digit == self.decimals && digit != 0 {
+                text.push_front(b'.');
+            }
+
+            text.push_front(DIGIT_CHARACTERS[(magnitude % base) as usize]);
+            magnitude /= base;
+            digit += 1;
+        }
+
+        if number < 0 {
+            text.push_front(b'-');
+        }
+
+        text
+    }
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Characters of a formatted number, filled from the end,
+/// so the number doesn't have to be allocated.
+struct NumberText {
+    characters: [u8; MAX_CHARACTERS],
+    start: usize,
+}
+
+impl NumberText {
+    fn new() -> Self {
+        Self {
+            characters: [0; MAX_CHARACTERS],
+            start: MAX_CHARACTERS,
+        }
+    }
+
+    fn push_front(&mut self, character: u8) {
+        self.start -= 1;
+        self.characters[self.start] = character;
+    }
+
+    fn as_str(&self) -> &str {
+        // only ascii characters are pushed
+        core::str::from_utf8(&self.characters[self.start..]).unwrap()
+    }
+}
@@ -0,0 +1,196 @@
+use stm32f1xx_hal::{backup_domain::BackupDomain, pac, rtc::Rtc};
+
+use crate::rtc_epoch::{EPOCH_REGISTER, EPOCH_REGISTERS};
+
+/// Backup data register with the correction, right after the epoch.
+/// Only its low byte is taken, the high one keeps the hour format.
+pub const CALIBRATION_REGISTER: usize = EPOCH_REGISTER + EPOCH_REGISTERS;
+
+/// Two backup data registers with the ticks
+/// the time was last set at, low and high half.
+pub const LAST_SET_REGISTER: usize = CALIBRATION_REGISTER + 1;
+
+/// Most the clock may be corrected by when it's running fast, in ppm.
+/// That's the maximum of the calibration register.
+pub const MAX_PPM: i8 = 121;
+
+/// Most the clock may be corrected by when it's running slow, in ppm.
+/// That's the speed up of dropping a cycle from the prescaler.
+pub const MIN_PPM: i8 = -30;
+
+/// Shortest time between two time corrections
+/// the drift is measured from, in seconds.
+/// Time is set by hand, within a second,
+/// shorter periods are not precise enough.
+pub const MIN_MEASURED_PERIOD: u32 = 7 * 24 * 60 * 60;
+
+/// Drift over this, in ppm, is not considered drift of the clock,
+/// but a change of the time, ie. when the time is set for the first time.
+const MAX_MEASURED_DRIFT: i64 = 200;
+
+/// RTC clock cycles per second, without the correction.
+const RTC_FREQUENCY: u32 = 32_768;
+
+/// The calibration register ignores its value of cycles
+/// every 2^20 cycles of the RTC clock.
+const CALIBRATION_PERIOD: i64 = 1 << 20;
+
+const CALIBRATION_MASK: u32 = 0x7F;
+
+/// Correction of the RTC drift, in ppm.
+///
+/// The RTC can only be slowed down by the calibration register.
+/// Clocks running slow are sped up by dropping a cycle
+/// of the prescaler, then slowed down by the calibration register.
+///
+/// The drift is measured automatically from time corrections,
+/// the error of the clock since the time was last set
+/// is added to the correction.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Calibration {
+    ppm: i8,
+    last_set: Option<u32>,
+}
+
+impl Calibration {
+    pub fn new(ppm: i8) -> Self {
+        Self {
+            ppm: ppm.clamp(MIN_PPM, MAX_PPM),
+            last_set: None,
+        }
+    }
+
+    /// The correction, how much the clock runs fast
+    /// without it, negative if it runs slow.
+    pub fn ppm(&self) -> i8 {
+        self.ppm
+    }
+
+    /// Sets the correction, gets clamped to [MIN_PPM](`MIN_PPM`) - [MAX_PPM](`MAX_PPM`).
+    /// The drift measured so far was measured with the old correction,
+    /// the measurement starts over with the next time correction.
+    pub fn set_ppm(&mut self, ppm: i8) {
+        self.ppm = ppm.clamp(MIN_PPM, MAX_PPM);
+        self.last_set = None;
+    }
+
+    /// Ticks the time was last corrected at.
+    pub fn last_set(&self) -> Option<u32> {
+        self.last_set
+    }
+
+    /// Tells the time was corrected, from the old ticks to the new ones.
+    /// If it was last corrected long enough ago, the error of the clock
+    /// is added to the correction. Returns whether the correction changed.
+    pub fn time_set(&mut self, old_ticks: u32, new_ticks: u32) -> bool {
+        let last_set = self.last_set.replace(new_ticks);
+        let elapsed = match last_set {
+            Some(last_set) if old_ticks.saturating_sub(last_set) >= MIN_MEASURED_PERIOD => {
+                (old_ticks - last_set) as i64
+            }
+            _ => return false,
+        };
+
+        let error = old_ticks as i64 - new_ticks as i64;
+        let drift = (error * 1_000_000 + error.signum() * elapsed / 2) / elapsed;
+        if drift == 0 || drift.abs() > MAX_MEASURED_DRIFT {
+            return false;
+        }
+
+        let ppm = (self.ppm as i64 + drift).clamp(MIN_PPM as i64, MAX_PPM as i64) as i8;
+        let changed = ppm != self.ppm;
+        self.ppm = ppm;
+        changed
+    }
+
+    /// Tells the time was moved by the given seconds, without being corrected,
+    /// ie. only the date was changed. The time of the last correction moves along,
+    /// so the drift measurement is not affected.
+    pub fn time_shifted(&mut self, seconds: i64) {
+        self.last_set = self
+            .last_set
+            .map(|last_set| (last_set as i64 + seconds).clamp(1, u32::MAX as i64) as u32);
+    }
+
+    pub fn load(backup: &BackupDomain) -> Self {
+        let low = backup.read_data_register_low(LAST_SET_REGISTER) as u32;
+        let high = backup.read_data_register_low(LAST_SET_REGISTER + 1) as u32;
+        let last_set = (high << 16) | low;
+
+        Self {
+            ppm: (backup.read_data_register_low(CALIBRATION_REGISTER) as i8)
+                .clamp(MIN_PPM, MAX_PPM),
+            last_set: if last_set != 0 { Some(last_set) } else { None },
+        }
+    }
+
+    pub fn save(&self, backup: &mut BackupDomain) {
+        let last_set = self.last_set.unwrap_or(0);
+
+        let data = backup.read_data_register_low(CALIBRATION_REGISTER) & 0xFF00;
+        backup.write_data_register_low(CALIBRATION_REGISTER, data | self.ppm as u8 as u16);
+        backup.write_data_register_low(LAST_SET_REGISTER, last_set as u16);
+        backup.write_data_register_low(LAST_SET_REGISTER + 1, (last_set >> 16) as u16);
+    }
+
+    /// Writes the correction to the RTC prescaler and the calibration register.
+    /// Writing the prescaler reloads it, so the next second starts now.
+    pub fn apply(&self, _rtc: &mut Rtc, backup: &mut BackupDomain) {
+        let (reload, _) = self.registers();
+
+        // the registers are owned by the RTC, borrowed mutably above
+        let rtc = unsafe { &*pac::RTC::ptr() };
+
+        while rtc.crl.read().rtoff().bit_is_clear() {}
+        rtc.crl.modify(|_, w| w.cnf().set_bit());
+        rtc.prlh.write(|w| unsafe { w.bits(reload >> 16) });
+        rtc.prll.write(|w| unsafe { w.bits(reload & 0xFFFF) });
+        rtc.crl.modify(|_, w| w.cnf().clear_bit());
+        while rtc.crl.read().rtoff().bit_is_clear() {}
+
+        self.apply_calibration_register(backup);
+    }
+
+    /// Writes the correction to the calibration register only,
+    /// the current second is not restarted. Enough if the prescaler
+    /// stays the same, see [reload_changed](`Calibration::reload_changed`).
+    ///
+    /// The backup domain is not used, it's taken to make sure
+    /// it's writable, as the calibration register is in it.
+    pub fn apply_calibration_register(&self, _backup: &mut BackupDomain) {
+        let (_, calibration) = self.registers();
+
+        // the register is in the backup domain, borrowed mutably above
+        let bkp = unsafe { &*pac::BKP::ptr() };
+        bkp.rtccr
+            .modify(|r, w| unsafe { w.bits((r.bits() & !CALIBRATION_MASK) | calibration) });
+    }
+
+    /// Whether the prescaler differs from the one of the other correction,
+    /// ie. if one of them corrects a clock running slow and the other one not.
+    pub fn reload_changed(&self, other: &Calibration) -> bool {
+        self.registers().0 != other.registers().0
+    }
+
+    /// Values of the prescaler reload register
+    /// and of the calibration register.
+    pub fn registers(&self) -> (u32, u32) {
+        // cycles to ignore every calibration period, rounded
+        let cycles =
+            (self.ppm as i64 * CALIBRATION_PERIOD + self.ppm.signum() as i64 * 500_000) / 1_000_000;
+
+        if cycles >= 0 {
+            (RTC_FREQUENCY - 1, cycles as u32)
+        } else {
+            // one cycle less a second is this many cycles more every period
+            let speed_up = CALIBRATION_PERIOD / (RTC_FREQUENCY as i64 - 1);
+            (RTC_FREQUENCY - 2, (speed_up + cycles).max(0) as u32)
+        }
+    }
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
@@ -0,0 +1,125 @@
+use stm32f1xx_hal::{backup_domain::BackupDomain, rtc::Rtc};
+
+use crate::{alarm::ALARM_DAYS_REGISTER, calendar::Calendar, time_zone::TimeZone};
+
+/// First backup data register used for the epoch, right after the alarms.
+/// The epoch occupies two registers, marker with format version and base year.
+pub const EPOCH_REGISTER: usize = ALARM_DAYS_REGISTER + 1;
+
+/// How many backup data registers the epoch occupies.
+pub const EPOCH_REGISTERS: usize = 2;
+
+const MARKER_REGISTER: usize = EPOCH_REGISTER;
+const BASE_YEAR_REGISTER: usize = EPOCH_REGISTER + 1;
+
+/// Marks that the epoch registers were written by this firmware,
+/// and so that the counter holds UTC. Firmware that did not
+/// write the marker counted local time.
+/// It's in the high byte of the marker register, the format version in the low one.
+/// Zeroed registers, ie. after the backup domain was reset, are not valid.
+const MARKER: u16 = 0xC1 << 8;
+
+/// Version of the format of the epoch registers.
+pub const FORMAT_VERSION: u16 = 1;
+
+/// Base year the counter was counted from by firmware
+/// that didn't store the epoch.
+pub const LEGACY_BASE_YEAR: u16 = 2023;
+
+/// Base year of the RTC counter, the counter holds
+/// the UTC seconds elapsed since the start of the base year.
+/// It's kept in the backup domain, along with the counter,
+/// so the counter can always be mapped back to the right date.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Epoch {
+    base_year: u16,
+}
+
+impl Epoch {
+    pub fn new(base_year: u16) -> Self {
+        Self {
+            base_year: base_year.max(1),
+        }
+    }
+
+    pub fn base_year(&self) -> u16 {
+        self.base_year
+    }
+
+    /// Reads the epoch from the backup domain.
+    /// None if the registers were not written,
+    /// or were written in an unknown format.
+    pub fn load(backup: &BackupDomain) -> Option<Self> {
+        let marker = backup.read_data_register_low(MARKER_REGISTER);
+        let base_year = backup.read_data_register_low(BASE_YEAR_REGISTER);
+
+        if marker != MARKER | FORMAT_VERSION || base_year == 0 {
+            return None;
+        }
+
+        Some(Self { base_year })
+    }
+
+    pub fn save(&self, backup: &mut BackupDomain) {
+        backup.write_data_register_low(BASE_YEAR_REGISTER, self.base_year);
+        // written last, the epoch is not valid until everything is written
+        backup.write_data_register_low(MARKER_REGISTER, MARKER | FORMAT_VERSION);
+    }
+
+    /// Finds out the epoch the RTC counter is counted from and makes sure
+    /// it's counted from the given one, migrating the counter if it's not.
+    /// The epoch is then saved to the backup domain.
+    ///
+    /// A new RTC, one that was not running, is counted from the given epoch.
+    /// A running RTC without a stored epoch was set by firmware
+    /// that did not store it, it's counted from [LEGACY_BASE_YEAR](`LEGACY_BASE_YEAR`)
+    /// in local time of the time zone, it's converted to UTC once.
+    pub fn restore(
+        rtc: &mut Rtc,
+        backup: &mut BackupDomain,
+        new_rtc: bool,
+        epoch: Self,
+        time_zone: TimeZone,
+    ) -> Self {
+        let ticks = rtc.current_time();
+        match Self::load(backup) {
+            _ if new_rtc => (),
+            Some(current) if current != epoch => {
+                rtc.set_time(current.migrate_ticks(ticks, epoch));
+            }
+            Some(_) => (),
+            None => {
+                let legacy = Self::new(LEGACY_BASE_YEAR);
+                rtc.set_time(legacy.migrate_local_ticks(ticks, epoch, time_zone));
+            }
+        }
+
+        epoch.save(backup);
+        epoch
+    }
+
+    /// Converts ticks counted from this epoch to the other one.
+    /// Dates outside of the range of the other epoch get clamped to it.
+    pub fn migrate_ticks(&self, ticks: u32, epoch: Self) -> u32 {
+        let date = Calendar::from_ticks(self.base_year, ticks);
+        let start = Calendar::new(0, 0, 0, 1, 1, epoch.base_year);
+
+        date.seconds_since(&start).clamp(0, u32::MAX as i64) as u32
+    }
+
+    /// Like [migrate_ticks](`Epoch::migrate_ticks`), but the ticks
+    /// are in local time of the time zone, they are converted to UTC.
+    pub fn migrate_local_ticks(&self, ticks: u32, epoch: Self, time_zone: TimeZone) -> u32 {
+        let local = Calendar::from_ticks(self.base_year, ticks);
+        let mut date = Calendar::new(0, 0, 0, 1, 1, epoch.base_year);
+        date.set_time_zone(time_zone);
+
+        date.set_year(local.year());
+        date.set_month(local.month());
+        date.set_day(local.day());
+        date.set_hours(local.hours());
+        date.set_minutes(local.minutes());
+        date.set_seconds(local.seconds());
+        date.to_ticks()
+    }
+}
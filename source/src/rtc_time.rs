@@ -0,0 +1,76 @@
+use stm32f1xx_hal::{pac, rtc::Rtc};
+
+/// Frequency of the RTC clock, the LSE crystal.
+/// The prescaler divides it down to one tick per second.
+const RTC_FREQUENCY: u32 = 32_768;
+
+const MILLIS_IN_SECOND: u32 = 1000;
+
+/// Time of the RTC with millisecond resolution,
+/// the ticks of the counter and the milliseconds
+/// elapsed since the last tick.
+///
+/// The milliseconds are computed from the prescaler divider,
+/// it counts down the RTC clock cycles remaining until the next tick.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, defmt::Format)]
+pub struct RtcTime {
+    ticks: u32,
+    millis: u16,
+}
+
+impl RtcTime {
+    pub fn new(ticks: u32, millis: u16) -> Self {
+        Self {
+            ticks: ticks.saturating_add(millis as u32 / MILLIS_IN_SECOND),
+            millis: (millis as u32 % MILLIS_IN_SECOND) as u16,
+        }
+    }
+
+    /// Reads the counter along with the divider.
+    ///
+    /// The divider is reloaded and the counter incremented at the same time,
+    /// if that happens in between the reads, they are read again,
+    /// so the milliseconds always belong to the returned ticks.
+    pub fn now(rtc: &Rtc) -> Self {
+        loop {
+            let ticks = rtc.current_time();
+            let divider = Self::divider();
+
+            if rtc.current_time() == ticks {
+                let elapsed = RTC_FREQUENCY - 1 - divider.min(RTC_FREQUENCY - 1);
+                return Self::new(ticks, (elapsed * MILLIS_IN_SECOND / RTC_FREQUENCY) as u16);
+            }
+        }
+    }
+
+    /// Reads the prescaler divider, it's split in two registers.
+    fn divider() -> u32 {
+        // the divider registers are read only, reading them has no side effects
+        let rtc = unsafe { &*pac::RTC::ptr() };
+
+        loop {
+            let high = rtc.divh.read().bits();
+            let low = rtc.divl.read().bits();
+
+            if rtc.divh.read().bits() == high {
+                return ((high & 0xF) << 16) | (low & 0xFFFF);
+            }
+        }
+    }
+
+    pub fn ticks(&self) -> u32 {
+        self.ticks
+    }
+
+    /// Milliseconds elapsed since the last tick, 0 - 999.
+    pub fn millis(&self) -> u16 {
+        self.millis
+    }
+
+    /// Milliseconds elapsed from the other time to this one,
+    /// negative if the other one is later.
+    pub fn millis_since(&self, other: &RtcTime) -> i64 {
+        (self.ticks as i64 - other.ticks as i64) * MILLIS_IN_SECOND as i64 + self.millis as i64
+            - other.millis as i64
+    }
+}
@@ -1,5 +1,92 @@
+/// Segments of a digit are encoded in a byte,
+/// a b c d e f g from the highest bit, the lowest bit is the dot.
 pub struct SevenSegments;
 
+/// Segments shown for characters that cannot be rendered,
+/// three horizontal bars. No supported character looks like that.
+pub const UNRENDERABLE: u8 = 0b1001001 << 1;
+
+/// Segments of the supported characters, a b c d e f g.
+/// Most of the letters are approximations, some of them
+/// look the same in both cases, or like a digit.
+const GLYPHS: [(char, u8); 75] = [
+    ('0', 0b1111110),
+    ('1', 0b0110000),
+    ('2', 0b1101101),
+    ('3', 0b1111001),
+    ('4', 0b0110011),
+    ('5', 0b1011011),
+    ('6', 0b1011111),
+    ('7', 0b1110000),
+    ('8', 0b1111111),
+    ('9', 0b1111011),
+    ('A', 0b1110111),
+    ('B', 0b0011111), // like b
+    ('C', 0b1001110),
+    ('D', 0b0111101), // like d
+    ('E', 0b1001111),
+    ('F', 0b1000111),
+    ('G', 0b1011110),
+    ('H', 0b0110111),
+    ('I', 0b0000110),
+    ('J', 0b0111100),
+    ('K', 0b1010111), // like H with a top bar
+    ('L', 0b0001110),
+    ('M', 0b1110110), // looks like upside down U
+    ('N', 0b0010101), // like n
+    ('O', 0b1111110), // like 0
+    ('P', 0b1100111),
+    ('Q', 0b1110011), // like q
+    ('R', 0b0000101), // like r
+    ('S', 0b1011011), // like 5
+    ('T', 0b0001111), // like t
+    ('U', 0b0111110),
+    ('V', 0b0111110), // like U
+    ('W', 0b0111110), // looks like U
+    ('X', 0b0110111), // like H
+    ('Y', 0b0111011),
+    ('Z', 0b1101101), // like 2
+    ('a', 0b1111101),
+    ('b', 0b0011111),
+    ('c', 0b0001101),
+    ('d', 0b0111101),
+    ('e', 0b1101111),
+    ('f', 0b1000111), // like F
+    ('g', 0b1111011), // like 9
+    ('h', 0b0010111),
+    ('i', 0b0010000),
+    ('j', 0b0111000),
+    ('k', 0b1010111), // like K
+    ('l', 0b0000110), // like I
+    ('m', 0b1010101), // like n with a top bar
+    ('n', 0b0010101),
+    ('o', 0b0011101),
+    ('p', 0b1100111), // like P
+    ('q', 0b1110011),
+    ('r', 0b0000101),
+    ('s', 0b1011011), // like 5
+    ('t', 0b0001111),
+    ('u', 0b0011100),
+    ('v', 0b0011100), // like u
+    ('w', 0b0011100), // like u
+    ('x', 0b0110111), // like H
+    ('y', 0b0111011),
+    ('z', 0b1101101), // like 2
+    (' ', 0b0000000),
+    ('-', 0b0000001),
+    ('_', 0b0001000),
+    ('°', 0b1100011),
+    ('=', 0b0001001),
+    ('?', 0b1100101),
+    ('"', 0b0100010),
+    ('\'', 0b0000010),
+    ('|', 0b0000110),
+    ('[', 0b1001110),
+    (']', 0b1111000),
+    ('(', 0b1001110),
+    (')', 0b1111000),
+];
+
 impl SevenSegments {
     pub fn digit_to_segments(digit: u8) -> u8 {
         (match digit {
@@ -17,12 +104,26 @@ impl SevenSegments {
         }) << 1
     }
 
+    /// Segments of the character,
+    /// [UNRENDERABLE](`UNRENDERABLE`) if it's not supported.
     pub fn letter_to_segments(letter: char) -> u8 {
-        (match letter {
-            'E' => 0b1001111,
-            'r' => 0b0000101,
-            'o' => 0b0011101,
-            _ => 0b0000001,
-        }) << 1
+        Self::char_to_segments(letter).unwrap_or(UNRENDERABLE)
+    }
+
+    /// Segments of the character, None if it's not supported.
+    pub fn char_to_segments(character: char) -> Option<u8> {
+        GLYPHS
+            .iter()
+            .find(|(glyph, _)| *glyph == character)
+            .map(|(_, segments)| segments << 1)
+    }
+
+    pub fn is_supported(character: char) -> bool {
+        Self::char_to_segments(character).is_some()
+    }
+
+    /// All the characters that may be rendered.
+    pub fn supported() -> impl Iterator<Item = char> {
+        GLYPHS.iter().map(|(glyph, _)| *glyph)
     }
 }
@@ -0,0 +1,133 @@
+use crate::mono_timer::Instant;
+
+/// How many lap times are remembered,
+/// older laps are dropped when a new one is taken.
+pub const STOPWATCH_LAPS: usize = 10;
+
+/// Stopwatch measuring time in hundredths of a second,
+/// using the instants of the [MonoTimer](`crate::mono_timer::MonoTimer`).
+///
+/// The instants don't overflow, so the stopwatch can simply
+/// compare the current instant against the one it was started at,
+/// the caller passes in the current instant.
+/// Setting the time of the RTC doesn't move the stopwatch.
+pub struct Stopwatch {
+    running: bool,
+    elapsed_millis: u64,
+    started: Option<Instant>,
+    splits: [u32; STOPWATCH_LAPS],
+    splits_count: usize,
+    /// Split of the last lap dropped, the oldest lap kept is timed from it.
+    dropped_split: u32,
+}
+
+impl Stopwatch {
+    pub fn new() -> Self {
+        Self {
+            running: false,
+            elapsed_millis: 0,
+            started: None,
+            splits: [0; STOPWATCH_LAPS],
+            splits_count: 0,
+            dropped_split: 0,
+        }
+    }
+
+    pub fn running(&self) -> bool {
+        self.running
+    }
+
+    pub fn start(&mut self, now: Instant) {
+        if !self.running {
+            self.running = true;
+            self.started = Some(now);
+        }
+    }
+
+    pub fn stop(&mut self, now: Instant) {
+        if self.running {
+            self.elapsed_millis = self.elapsed_millis(now);
+            self.running = false;
+            self.started = None;
+        }
+    }
+
+    pub fn toggle(&mut self, now: Instant) {
+        if self.running {
+            self.stop(now);
+        } else {
+            self.start(now);
+        }
+    }
+
+    /// Stops the stopwatch, clearing the elapsed time and the laps.
+    pub fn reset(&mut self) {
+        self.running = false;
+        self.started = None;
+        self.elapsed_millis = 0;
+        self.splits_count = 0;
+        self.dropped_split = 0;
+    }
+
+    /// Saves the current time as a split time,
+    /// dropping the oldest one if there are already
+    /// [STOPWATCH_LAPS](`STOPWATCH_LAPS`) of them.
+    pub fn lap(&mut self, now: Instant) {
+        let elapsed = self.elapsed(now);
+
+        if self.splits_count == STOPWATCH_LAPS {
+            self.dropped_split = self.splits[0];
+            self.splits.rotate_left(1);
+            self.splits_count -= 1;
+        }
+
+        self.splits[self.splits_count] = elapsed;
+        self.splits_count += 1;
+    }
+
+    pub fn laps_count(&self) -> usize {
+        self.splits_count
+    }
+
+    /// Total time elapsed when the given lap was taken,
+    /// in hundredths of a second.
+    pub fn split(&self, index: usize) -> Option<u32> {
+        if index < self.splits_count {
+            Some(self.splits[index])
+        } else {
+            None
+        }
+    }
+
+    /// Time of the given lap only, in hundredths of a second.
+    pub fn lap_time(&self, index: usize) -> Option<u32> {
+        let split = self.split(index)?;
+        let previous = if index > 0 {
+            self.splits[index - 1]
+        } else {
+            self.dropped_split
+        };
+
+        Some(split - previous)
+    }
+
+    /// Elapsed time in hundredths of a second.
+    pub fn elapsed(&self, now: Instant) -> u32 {
+        (self.elapsed_millis(now) / 10) as u32
+    }
+
+    fn elapsed_millis(&self, now: Instant) -> u64 {
+        let running = self
+            .started
+            .map(|started| now.duration_since(started).as_millis())
+            .unwrap_or(0);
+
+        self.elapsed_millis + running
+    }
+}
+
+impl Default for Stopwatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
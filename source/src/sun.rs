@@ -0,0 +1,136 @@
+use core::f32::consts::PI;
+
+use libm::{acosf, cosf, sinf, tanf};
+
+use crate::calendar::Calendar;
+
+/// Zenith of the sun at sunrise and sunset, in degrees.
+/// It's over 90 due to refraction and the size of the sun.
+const SUNRISE_ZENITH: f32 = 90.833;
+
+/// Zenith of the sun at the start of the morning
+/// and at the end of the evening civil twilight, in degrees.
+const CIVIL_TWILIGHT_ZENITH: f32 = 96.0;
+
+const MINUTES_IN_DAY: i32 = 24 * 60;
+
+/// Place on the Earth, in degrees.
+/// North latitude and east longitude are positive.
+#[derive(Copy, Clone, PartialEq)]
+pub struct Location {
+    latitude: f32,
+    longitude: f32,
+}
+
+impl Location {
+    pub const fn new(latitude: f32, longitude: f32) -> Self {
+        Self {
+            latitude,
+            longitude,
+        }
+    }
+
+    pub fn latitude(&self) -> f32 {
+        self.latitude
+    }
+
+    pub fn longitude(&self) -> f32 {
+        self.longitude
+    }
+
+    /// Calculates when the sun rises and sets on the date of the calendar,
+    /// in the local time of the calendar. Uses the NOAA approximation,
+    /// the times are within a few minutes of the exact ones.
+    pub fn sun_times(&self, calendar: &Calendar) -> SunTimes {
+        let days_in_year = if Calendar::is_leap_year(calendar.year()) {
+            366.0
+        } else {
+            365.0
+        };
+
+        // fractional year at noon, in radians
+        let year = 2.0 * PI / days_in_year * (calendar.day_of_year() - 1) as f32;
+
+        let equation_of_time = 229.18
+            * (0.000075 + 0.001868 * cosf(year)
+                - 0.032077 * sinf(year)
+                - 0.014615 * cosf(2.0 * year)
+                - 0.040849 * sinf(2.0 * year));
+        let declination = 0.006918 - 0.399912 * cosf(year) + 0.070257 * sinf(year)
+            - 0.006758 * cosf(2.0 * year)
+            + 0.000907 * sinf(2.0 * year)
+            - 0.002697 * cosf(3.0 * year)
+            + 0.00148 * sinf(3.0 * year);
+
+        // solar noon in local time, in minutes
+        let noon =
+            720.0 - 4.0 * self.longitude - equation_of_time + calendar.utc_offset() as f32 / 60.0;
+
+        let latitude = self.latitude.to_radians();
+        let event = |zenith: f32, morning: bool| {
+            let cos_hour_angle = cosf(zenith.to_radians()) / (cosf(latitude) * cosf(declination))
+                - tanf(latitude) * tanf(declination);
+            if !(-1.0..=1.0).contains(&cos_hour_angle) {
+                // the sun stays above or below the zenith the whole day
+                return None;
+            }
+
+            let hour_angle = acosf(cos_hour_angle).to_degrees();
+            let minutes = if morning {
+                noon - 4.0 * hour_angle
+            } else {
+                noon + 4.0 * hour_angle
+            };
+            Some(Self::minutes_in_day(minutes))
+        };
+
+        SunTimes {
+            dawn: event(CIVIL_TWILIGHT_ZENITH, true),
+            sunrise: event(SUNRISE_ZENITH, true),
+            noon: Self::minutes_in_day(noon),
+            sunset: event(SUNRISE_ZENITH, false),
+            dusk: event(CIVIL_TWILIGHT_ZENITH, false),
+        }
+    }
+
+    fn minutes_in_day(minutes: f32) -> u16 {
+        ((minutes + 0.5) as i32).rem_euclid(MINUTES_IN_DAY) as u16
+    }
+}
+
+/// Times of the sun events of a day, in minutes from the local midnight.
+/// Events that don't happen on the day, ie. during polar day or night,
+/// are None.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct SunTimes {
+    dawn: Option<u16>,
+    sunrise: Option<u16>,
+    noon: u16,
+    sunset: Option<u16>,
+    dusk: Option<u16>,
+}
+
+impl SunTimes {
+    /// Start of the morning civil twilight.
+    pub fn dawn(&self) -> Option<u16> {
+        self.dawn
+    }
+
+    pub fn sunrise(&self) -> Option<u16> {
+        self.sunrise
+    }
+
+    /// When the sun is the highest.
+    pub fn noon(&self) -> u16 {
+        self.noon
+    }
+
+    pub fn sunset(&self) -> Option<u16> {
+        self.sunset
+    }
+
+    /// End of the evening civil twilight.
+    pub fn dusk(&self) -> Option<u16> {
+        self.dusk
+    }
+}
@@ -0,0 +1,111 @@
+use crate::calendar::Calendar;
+
+const DST_SECONDS: i32 = 60 * 60;
+
+/// When daylight saving time is observed.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum DstRule {
+    /// Standard time during the whole year.
+    None,
+    /// From last Sunday of March 01:00 UTC
+    /// to last Sunday of October 01:00 UTC.
+    EuropeanUnion,
+    /// From second Sunday of March 02:00 local time
+    /// to first Sunday of November 02:00 local time.
+    UnitedStates,
+}
+
+enum Sunday {
+    Nth(u8),
+    Last,
+}
+
+/// Offset of local standard time from UTC,
+/// and the daylight saving time rule used.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct TimeZone {
+    offset: i32,
+    dst: DstRule,
+}
+
+impl TimeZone {
+    pub const UTC: TimeZone = TimeZone::new(0, DstRule::None);
+
+    pub const fn new(offset_minutes: i16, dst: DstRule) -> Self {
+        Self {
+            offset: offset_minutes as i32 * 60,
+            dst,
+        }
+    }
+
+    /// Offset of local standard time from UTC, in seconds.
+    pub fn standard_offset(&self) -> i32 {
+        self.offset
+    }
+
+    pub fn dst(&self) -> DstRule {
+        self.dst
+    }
+
+    /// Offset of local time from UTC at the given
+    /// Unix timestamp, in seconds, daylight saving time included.
+    pub fn offset(&self, timestamp: u32) -> i32 {
+        let year = Calendar::from_unix_timestamp(1970, timestamp).year();
+        match self.dst_period(year) {
+            Some((start, end)) if start <= timestamp && timestamp < end => {
+                self.offset + DST_SECONDS
+            }
+            _ => self.offset,
+        }
+    }
+
+    /// Unix timestamp of the first second with a different offset
+    /// than the given timestamp has. None if the offset never changes.
+    pub fn next_transition(&self, timestamp: u32) -> Option<u32> {
+        let year = Calendar::from_unix_timestamp(1970, timestamp).year();
+
+        [year, year + 1]
+            .into_iter()
+            .filter_map(|year| self.dst_period(year))
+            .flat_map(|(start, end)| [start, end])
+            .find(|&transition| transition > timestamp)
+    }
+
+    /// Unix timestamps of the start and the end of daylight saving time
+    /// in the given year. The end is the first second of standard time.
+    fn dst_period(&self, year: u16) -> Option<(u32, u32)> {
+        let hour = 60 * 60;
+        let (start, end) = match self.dst {
+            DstRule::None => return None,
+            DstRule::EuropeanUnion => (
+                Self::sunday(year, 3, Sunday::Last)? + hour,
+                Self::sunday(year, 10, Sunday::Last)? + hour,
+            ),
+            DstRule::UnitedStates => (
+                Self::sunday(year, 3, Sunday::Nth(2))? + 2 * hour - self.offset as i64,
+                Self::sunday(year, 11, Sunday::Nth(1))? + 2 * hour
+                    - (self.offset + DST_SECONDS) as i64,
+            ),
+        };
+
+        Some((start.try_into().ok()?, end.try_into().ok()?))
+    }
+
+    /// Unix timestamp of the midnight starting the given Sunday of the month.
+    fn sunday(year: u16, month: u8, sunday: Sunday) -> Option<i64> {
+        let first = Calendar::new(0, 0, 0, 1, month, year);
+        let first_sunday = 1 + (6 - first.weekday() as u8);
+        let day = match sunday {
+            Sunday::Nth(n) => first_sunday + 7 * (n - 1),
+            Sunday::Last => {
+                let days_in_month =
+                    Calendar::days_in_month(month, Calendar::is_leap_year(year)).ok()?;
+                first_sunday + (days_in_month - first_sunday) / 7 * 7
+            }
+        };
+
+        Calendar::new(0, 0, 0, day, month, year)
+            .to_unix_timestamp()
+            .map(|timestamp| timestamp as i64)
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{
+    brightness_manager::LOWEST_DISPLAY_BRIGHTNESS, clock_display::ClockDisplay, mono_timer::Instant,
+};
+
+/// Digits of the whole display, transitions are always over all of them.
+const DIGITS: usize = 8;
+
+/// Time a transition takes, in milliseconds,
+/// noticeable without delaying the new view too much.
+pub const DEFAULT_DURATION_MILLIS: u32 = 400;
+
+/// Progress of a finished transition, progress is in per mille.
+const FULL_PROGRESS: u32 = 1000;
+
+const SEGMENT_A: u8 = 1 << 7;
+const SEGMENT_B: u8 = 1 << 6;
+const SEGMENT_C: u8 = 1 << 5;
+const SEGMENT_D: u8 = 1 << 4;
+const SEGMENT_E: u8 = 1 << 3;
+const SEGMENT_F: u8 = 1 << 2;
+const SEGMENT_G: u8 = 1 << 1;
+
+/// Segments moved by half a digit up, the top ones are dropped.
+const HALF_UP: [(u8, u8); 4] = [
+    (SEGMENT_G, SEGMENT_A),
+    (SEGMENT_C, SEGMENT_B),
+    (SEGMENT_E, SEGMENT_F),
+    (SEGMENT_D, SEGMENT_G),
+];
+
+/// Segments moved by half a digit down, the bottom ones are dropped.
+const HALF_DOWN: [(u8, u8); 4] = [
+    (SEGMENT_A, SEGMENT_G),
+    (SEGMENT_B, SEGMENT_C),
+    (SEGMENT_F, SEGMENT_E),
+    (SEGMENT_G, SEGMENT_D),
+];
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum TransitionKind {
+    /// The new frame pushes the old one out to the left.
+    SlideLeft,
+    /// The new frame pushes the old one out to the right.
+    SlideRight,
+    /// The digits that change roll up, the new ones come from below.
+    Roll,
+    /// The old frame fades out, then the new one fades in,
+    /// by the brightness of the digits, see [faded](`Transition::faded`).
+    Fade,
+}
+
+/// Animation from a frame shown on the display to the frame
+/// rendered after it, ie. when the view changes.
+///
+/// The new frame is rendered as usual, the transition
+/// is shown over it by [update](`Transition::update`),
+/// called from the display timer after everything is rendered.
+/// The new frame has to be put back before rendering again,
+/// see [target](`Transition::target`).
+pub struct Transition {
+    kind: TransitionKind,
+    from: [u8; DIGITS],
+    to: [u8; DIGITS],
+    duration_millis: u32,
+    progress: u32,
+    started: Option<Instant>,
+}
+
+impl Transition {
+    /// Creates a transition from the frame, taking duration_millis.
+    pub fn new(kind: TransitionKind, from: [u8; DIGITS], duration_millis: u32) -> Self {
+        Self {
+            kind,
+            from,
+            to: from,
+            duration_millis: duration_millis.max(1),
+            progress: 0,
+            started: None,
+        }
+    }
+
+    pub fn kind(&self) -> TransitionKind {
+        self.kind
+    }
+
+    pub fn duration_millis(&self) -> u32 {
+        self.duration_millis
+    }
+
+    /// Progress of the transition, in per mille.
+    pub fn progress(&self) -> u32 {
+        self.progress
+    }
+
+    pub fn finished(&self) -> bool {
+        self.progress >= FULL_PROGRESS
+    }
+
+    /// The new frame, as rendered at the last update.
+    pub fn target(&self) -> [u8; DIGITS] {
+        self.to
+    }
+
+    /// Sets the new frame, it's taken from the display on every update.
+    pub fn set_target(&mut self, to: [u8; DIGITS]) {
+        self.to = to;
+    }
+
+    /// Shows the transition from the old frame to the frame
+    /// on the display, at the time elapsed since the first update.
+    /// Returns whether the transition is finished,
+    /// the new frame is shown as it is then.
+    pub fn update(&mut self, display: &mut ClockDisplay, now: Instant) -> bool {
+        let started = *self.started.get_or_insert(now);
+        let elapsed = now.duration_since(started).as_millis();
+        self.progress = (elapsed * FULL_PROGRESS as u64 / self.duration_millis as u64)
+            .min(FULL_PROGRESS as u64) as u32;
+
+        let display = display.display();
+        self.set_target(display.data());
+        display.set_data(self.frame(self.progress));
+
+        if self.kind == TransitionKind::Fade {
+            let level = Self::fade_level(self.progress);
+            let mut brightness = display.brightness();
+            for digit_brightness in brightness.iter_mut() {
+                *digit_brightness = Self::faded(*digit_brightness, level);
+            }
+            display.set_brightness(brightness);
+        }
+
+        self.finished()
+    }
+
+    /// Segments shown at the given progress, in per mille.
+    pub fn frame(&self, progress: u32) -> [u8; DIGITS] {
+        let progress = progress.min(FULL_PROGRESS);
+        let mut frame = [0; DIGITS];
+
+        match self.kind {
+            TransitionKind::SlideLeft => {
+                let step = progress as usize * DIGITS / FULL_PROGRESS as usize;
+                for (digit, segments) in frame.iter_mut().enumerate() {
+                    *segments = match digit + step {
+                        index if index < DIGITS => self.from[index],
+                        index => self.to[index - DIGITS],
+                    };
+                }
+            }
+            TransitionKind::SlideRight => {
+                let step = progress as usize * DIGITS / FULL_PROGRESS as usize;
+                for (digit, segments) in frame.iter_mut().enumerate() {
+                    *segments = match digit.checked_sub(step) {
+                        Some(index) => self.from[index],
+                        None => self.to[DIGITS - step + digit],
+                    };
+                }
+            }
+            TransitionKind::Roll => match progress * 3 / FULL_PROGRESS {
+                0 => frame = self.from,
+                // halfway, the bottom of the old digit over the top of the new one
+                1 => {
+                    for (digit, segments) in frame.iter_mut().enumerate() {
+                        let (from, to) = (self.from[digit], self.to[digit]);
+                        *segments = if from == to {
+                            to
+                        } else {
+                            Self::shifted(from, &HALF_UP) | Self::shifted(to, &HALF_DOWN)
+                        };
+                    }
+                }
+                _ => frame = self.to,
+            },
+            TransitionKind::Fade => {
+                frame = if progress < FULL_PROGRESS / 2 {
+                    self.from
+                } else {
+                    self.to
+                };
+            }
+        }
+
+        frame
+    }
+
+    /// Brightness of the digits when fading, in per mille,
+    /// going down to zero halfway, then up again.
+    pub fn fade_level(progress: u32) -> u32 {
+        let progress = progress.min(FULL_PROGRESS);
+        (FULL_PROGRESS as i32 - 2 * progress as i32).unsigned_abs()
+    }
+
+    /// Brightness of a digit at the fade level, in per mille.
+    /// The digit is dimmed only down to the lowest visible brightness,
+    /// [LOWEST_DISPLAY_BRIGHTNESS](`LOWEST_DISPLAY_BRIGHTNESS`),
+    /// so it doesn't disappear right after the fade starts.
+    pub fn faded(brightness: u16, level: u32) -> u16 {
+        match brightness.checked_sub(LOWEST_DISPLAY_BRIGHTNESS) {
+            Some(visible) => {
+                let level = level.min(FULL_PROGRESS);
+                LOWEST_DISPLAY_BRIGHTNESS + (visible as u32 * level / FULL_PROGRESS) as u16
+            }
+            None => brightness,
+        }
+    }
+
+    /// Moves the segments by the mapping, the dot is dropped.
+    fn shifted(segments: u8, mapping: &[(u8, u8); 4]) -> u8 {
+        mapping
+            .iter()
+            .filter(|(from, _)| segments & from != 0)
+            .fold(0, |shifted, (_, to)| shifted | to)
+    }
+}
@@ -0,0 +1,139 @@
+use chrono::{Datelike, Months, NaiveDate, NaiveDateTime, TimeDelta, Timelike};
+
+use crate::{
+    calendar::Calendar,
+    time_zone::{DstRule, TimeZone},
+};
+
+fn calendar(date: NaiveDateTime) -> Calendar {
+    Calendar::with_base_year(
+        2000,
+        date.hour() as u8,
+        date.minute() as u8,
+        date.second() as u8,
+        date.day() as u8,
+        date.month() as u8,
+        date.year() as u16,
+    )
+}
+
+fn assert_date(calendar: &Calendar, date: NaiveDateTime) {
+    assert_eq!(
+        (
+            calendar.year() as i32,
+            calendar.month() as u32,
+            calendar.day() as u32
+        ),
+        (date.year(), date.month(), date.day()),
+        "{date}"
+    );
+    assert_eq!(
+        (
+            calendar.hours() as u32,
+            calendar.minutes() as u32,
+            calendar.seconds() as u32
+        ),
+        (date.hour(), date.minute(), date.second()),
+        "{date}"
+    );
+}
+
+fn start() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2023, 1, 31)
+        .unwrap()
+        .and_hms_opt(13, 45, 10)
+        .unwrap()
+}
+
+#[test]
+fn add_seconds() {
+    for seconds in (-700_000_000i64..700_000_000).step_by(999_983) {
+        let mut result = calendar(start());
+        result.add_seconds(seconds);
+        assert_date(&result, start() + TimeDelta::seconds(seconds));
+    }
+}
+
+#[test]
+fn add_seconds_clamped() {
+    let mut result = calendar(start());
+    result.add_seconds(-1_000_000_000);
+    assert_eq!(result.to_ticks(), 0);
+    assert_eq!(result.year(), 2000);
+}
+
+#[test]
+fn add_days() {
+    for days in -8000..8000 {
+        let mut result = calendar(start());
+        result.add_days(days);
+        assert_date(&result, start() + TimeDelta::days(days as i64));
+    }
+}
+
+#[test]
+fn add_months() {
+    for months in -250..250 {
+        let mut result = calendar(start());
+        result.add_months(months);
+
+        let expected = if months >= 0 {
+            start() + Months::new(months as u32)
+        } else {
+            start() - Months::new(-months as u32)
+        };
+        assert_date(&result, expected);
+    }
+}
+
+#[test]
+fn add_months_clamps_day() {
+    let mut leap = Calendar::with_base_year(2000, 0, 0, 0, 31, 1, 2024);
+    leap.add_months(1);
+    assert_eq!((leap.day(), leap.month()), (29, 2));
+
+    let mut common = Calendar::with_base_year(2000, 0, 0, 0, 31, 3, 2023);
+    common.add_months(-1);
+    assert_eq!((common.day(), common.month()), (28, 2));
+}
+
+#[test]
+fn add_days_keeps_local_time() {
+    let mut calendar = Calendar::from_ticks(2023, 0);
+    calendar.set_time_zone(TimeZone::new(60, DstRule::EuropeanUnion));
+    calendar.add_days(180);
+    assert_eq!((calendar.day(), calendar.month()), (30, 6));
+    assert_eq!(calendar.hours(), 1);
+    assert_eq!(calendar.utc_offset(), 2 * 60 * 60);
+}
+
+#[test]
+fn seconds_since() {
+    let earlier = calendar(start());
+    let mut later = earlier.clone();
+    later.add_seconds(100_000);
+
+    assert_eq!(later.seconds_since(&earlier), 100_000);
+    assert_eq!(earlier.seconds_since(&later), -100_000);
+
+    let other_base = Calendar::with_base_year(1990, 13, 45, 10, 31, 1, 2023);
+    assert_eq!(other_base.seconds_since(&earlier), 0);
+}
+
+#[test]
+fn ordering() {
+    let earlier = calendar(start());
+    let mut later = earlier.clone();
+    later.add_seconds(1);
+
+    assert!(earlier < later);
+    assert!(later > earlier);
+    assert!(earlier.clone().max(later.clone()) == later);
+    assert!(earlier == Calendar::with_base_year(1990, 13, 45, 10, 31, 1, 2023));
+
+    // same instant in different time zones
+    let mut local = earlier.clone();
+    local.set_time_zone(TimeZone::new(-5 * 60, DstRule::None));
+    assert_eq!(local.hours(), 8);
+    assert!(local == earlier);
+}
@@ -0,0 +1,149 @@
+use crate::{
+    calendar::{Calendar, ParseError},
+    time_zone::{DstRule, TimeZone},
+};
+
+#[test]
+fn display() {
+    let calendar = Calendar::new(7, 5, 9, 3, 2, 2024);
+    assert_eq!(format!("{calendar}"), "2024-02-03T07:05:09Z");
+
+    let calendar = Calendar::from_unix_timestamp(1970, 0);
+    assert_eq!(format!("{calendar}"), "1970-01-01T00:00:00Z");
+}
+
+#[test]
+fn display_offset() {
+    // 1. 7. 2023 12:00:00 UTC
+    let mut calendar = Calendar::from_unix_timestamp(2000, 1688212800);
+
+    calendar.set_time_zone(TimeZone::new(60, DstRule::EuropeanUnion));
+    assert_eq!(format!("{calendar}"), "2023-07-01T14:00:00+02:00");
+
+    calendar.set_time_zone(TimeZone::new(-5 * 60, DstRule::UnitedStates));
+    assert_eq!(format!("{calendar}"), "2023-07-01T08:00:00-04:00");
+
+    calendar.set_time_zone(TimeZone::new(5 * 60 + 30, DstRule::None));
+    assert_eq!(format!("{calendar}"), "2023-07-01T17:30:00+05:30");
+}
+
+#[test]
+fn parse() {
+    let calendar = Calendar::parse_iso8601(2000, "2024-02-29T23:59:58")
+        .ok()
+        .unwrap();
+    assert!(calendar == Calendar::new(23, 59, 58, 29, 2, 2024));
+    assert_eq!(calendar.to_unix_timestamp(), Some(1709251198));
+
+    let calendar = Calendar::parse_iso8601(2000, "2024-02-29T23:59:58Z")
+        .ok()
+        .unwrap();
+    assert_eq!(calendar.to_unix_timestamp(), Some(1709251198));
+    assert_eq!(calendar.utc_offset(), 0);
+}
+
+#[test]
+fn parse_offset() {
+    let calendar = Calendar::parse_iso8601(2000, "2023-07-01T14:00:00+02:00")
+        .ok()
+        .unwrap();
+    assert_eq!((calendar.hours(), calendar.minutes()), (14, 0));
+    assert_eq!(calendar.utc_offset(), 2 * 60 * 60);
+    assert!(calendar == Calendar::from_unix_timestamp(2000, 1688212800));
+
+    let calendar = Calendar::parse_iso8601(2000, "2023-07-01T06:30:00-05:30")
+        .ok()
+        .unwrap();
+    assert_eq!(calendar.utc_offset(), -(5 * 60 + 30) * 60);
+    assert!(calendar == Calendar::from_unix_timestamp(2000, 1688212800));
+}
+
+#[test]
+fn round_trip() {
+    let mut calendar = Calendar::from_unix_timestamp(1970, 0);
+    calendar.set_time_zone(TimeZone::new(60, DstRule::EuropeanUnion));
+
+    for timestamp in (0..u32::MAX - 2 * 24 * 60 * 60).step_by(7919 * 97) {
+        calendar.add_seconds(timestamp as i64 - calendar.to_unix_timestamp().unwrap() as i64);
+        let text = format!("{calendar}");
+        let parsed = Calendar::parse_iso8601(1970, &text).ok().unwrap();
+
+        assert!(parsed == calendar, "{text}");
+        assert_eq!(format!("{parsed}"), text);
+    }
+}
+
+#[test]
+fn invalid_format() {
+    for text in [
+        "",
+        "2023-07-01",
+        "2023-07-01 14:00:00",
+        "2023/07/01T14:00:00",
+        "2023-7-01T14:00:00",
+        "2023-07-01T14:00:0a",
+        "2023-07-01T14:00:00+",
+        "2023-07-01T14:00:00+0200",
+        "2023-07-01T14:00:00z",
+        "2023-07-01T14:00:00Z ",
+        "+023-07-01T14:00:00",
+    ] {
+        assert_eq!(
+            Calendar::parse_iso8601(2000, text).err(),
+            Some(ParseError::InvalidFormat),
+            "{text}"
+        );
+    }
+}
+
+#[test]
+fn out_of_range() {
+    for text in [
+        "2023-00-01T14:00:00",
+        "2023-13-01T14:00:00",
+        "2023-02-29T14:00:00",
+        "2023-04-31T14:00:00",
+        "2023-07-00T14:00:00",
+        "2023-07-01T24:00:00",
+        "2023-07-01T14:60:00",
+        "2023-07-01T14:00:60",
+        "2023-07-01T14:00:00+24:00",
+        "2023-07-01T14:00:00+01:60",
+        // before the base year
+        "1999-12-31T23:59:59",
+        "2000-01-01T00:30:00+01:00",
+        // ticks would overflow
+        "2136-02-07T06:28:16",
+    ] {
+        assert_eq!(
+            Calendar::parse_iso8601(2000, text).err(),
+            Some(ParseError::OutOfRange),
+            "{text}"
+        );
+    }
+
+    assert!(Calendar::parse_iso8601(2000, "2000-01-01T00:30:00+00:30").is_ok());
+    assert!(Calendar::parse_iso8601(2000, "2136-02-07T06:28:15").is_ok());
+}
+
+#[test]
+fn base_year_zero() {
+    for text in [
+        "0000-01-01T00:00:00",
+        "0000-02-29T12:00:00",
+        "0000-03-01T00:00:00",
+    ] {
+        assert_eq!(
+            Calendar::parse_iso8601(0, text).err(),
+            Some(ParseError::OutOfRange),
+            "{text}"
+        );
+    }
+
+    // taken as base year 1
+    let calendar = Calendar::parse_iso8601(0, "0001-01-01T00:00:01")
+        .ok()
+        .unwrap();
+    assert_eq!(calendar.to_ticks(), 1);
+    assert_eq!(calendar.year(), 1);
+}
@@ -0,0 +1,72 @@
+#![cfg_attr(not(test), no_std)]
+
+#[path = "../../../source/src/calendar.rs"]
+pub mod calendar;
+
+#[path = "../../../source/src/moon.rs"]
+pub mod moon;
+
+#[path = "../../../source/src/sun.rs"]
+pub mod sun;
+
+#[path = "../../../source/src/time_zone.rs"]
+pub mod time_zone;
+
+#[cfg(test)]
+mod arithmetic;
+#[cfg(test)]
+mod iso8601;
+#[cfg(test)]
+mod moon_phase;
+#[cfg(test)]
+mod sun_times;
+#[cfg(test)]
+mod ticks;
+#[cfg(test)]
+mod time_zones;
+#[cfg(test)]
+mod unix_timestamp;
+#[cfg(test)]
+mod validation;
+
+/// Step through the whole range of seconds,
+/// prime, so that all the seconds of day get hit.
+#[cfg(test)]
+const STEP: usize = 7919;
+
+/// Seconds sampled from the whole range of `u32`,
+/// the last ones all included.
+#[cfg(test)]
+fn full_range() -> impl Iterator<Item = u32> {
+    (0..=u32::MAX)
+        .step_by(STEP)
+        .chain(u32::MAX - 2 * STEP as u32..=u32::MAX)
+}
+
+/// Start of every day in the range of `u32` seconds,
+/// each with the second before it.
+#[cfg(test)]
+fn every_day() -> impl Iterator<Item = u32> {
+    (0..=u32::MAX / (24 * 60 * 60)).flat_map(|day| {
+        let seconds = day * 24 * 60 * 60;
+        [seconds, seconds.saturating_sub(1)]
+    })
+}
+
+/// Asserts the date and time of the calendar match the reference,
+/// the conversion that made the calendar is named by the message.
+#[cfg(test)]
+fn assert_matches_reference(
+    calendar: &calendar::Calendar,
+    reference: chrono::NaiveDateTime,
+    message: &str,
+) {
+    use chrono::{Datelike, Timelike};
+
+    assert_eq!(calendar.year() as i32, reference.year(), "{message}");
+    assert_eq!(calendar.month() as u32, reference.month(), "{message}");
+    assert_eq!(calendar.day() as u32, reference.day(), "{message}");
+    assert_eq!(calendar.hours() as u32, reference.hour(), "{message}");
+    assert_eq!(calendar.minutes() as u32, reference.minute(), "{message}");
+    assert_eq!(calendar.seconds() as u32, reference.second(), "{message}");
+}
@@ -0,0 +1,81 @@
+use crate::{
+    calendar::Calendar,
+    moon::{MoonPhase, Phase},
+    time_zone::{DstRule, TimeZone},
+};
+
+/// The real moon may be more than half a day off the mean lunation,
+/// the illumination changes by up to 10 % a day.
+const ILLUMINATION_TOLERANCE: u8 = 8;
+
+fn moon_phase(hours: u8, minutes: u8, day: u8, month: u8, year: u16) -> MoonPhase {
+    MoonPhase::from_calendar(&Calendar::with_base_year(
+        1970, hours, minutes, 0, day, month, year,
+    ))
+}
+
+#[test]
+fn new_moon() {
+    let moon = moon_phase(11, 57, 11, 1, 2024);
+    assert_eq!(moon.phase(), Phase::NewMoon);
+    assert!(moon.illumination() <= 1);
+    assert!(moon.days() == 0 || moon.days() == 29);
+}
+
+#[test]
+fn first_quarter() {
+    let moon = moon_phase(3, 53, 18, 1, 2024);
+    assert_eq!(moon.phase(), Phase::FirstQuarter);
+    assert!(moon.illumination().abs_diff(50) <= ILLUMINATION_TOLERANCE);
+    assert!(moon.days().abs_diff(7) <= 1);
+}
+
+#[test]
+fn full_moon() {
+    let moon = moon_phase(17, 54, 25, 1, 2024);
+    assert_eq!(moon.phase(), Phase::FullMoon);
+    assert!(moon.illumination() >= 98);
+    assert!(moon.days().abs_diff(14) <= 1);
+}
+
+#[test]
+fn last_quarter() {
+    let moon = moon_phase(23, 18, 2, 2, 2024);
+    assert_eq!(moon.phase(), Phase::LastQuarter);
+    assert!(moon.illumination().abs_diff(50) <= ILLUMINATION_TOLERANCE);
+}
+
+#[test]
+fn before_reference() {
+    // full moon of 10. 1. 1990 4:57 UTC
+    let moon = moon_phase(4, 57, 11, 1, 1990);
+    assert_eq!(moon.phase(), Phase::FullMoon);
+}
+
+#[test]
+fn local_time() {
+    // the same instant as the full moon in UTC
+    let mut calendar = Calendar::with_base_year(1970, 17, 54, 0, 25, 1, 2024);
+    calendar.set_time_zone(TimeZone::new(-5 * 60, DstRule::UnitedStates));
+    assert_eq!(calendar.hours(), 12);
+    assert!(MoonPhase::from_calendar(&calendar) == moon_phase(17, 54, 25, 1, 2024));
+}
+
+#[test]
+fn phases_in_order() {
+    let mut calendar = Calendar::with_base_year(1970, 11, 57, 0, 11, 1, 2024);
+    let mut phase = Phase::NewMoon;
+    let mut changes = 0;
+
+    for _ in 0..30 * 24 {
+        calendar.add_seconds(60 * 60);
+        let next = MoonPhase::from_calendar(&calendar).phase();
+        if next != phase {
+            phase = next;
+            changes += 1;
+        }
+    }
+
+    assert_eq!(changes, 8);
+    assert_eq!(phase, Phase::NewMoon);
+}
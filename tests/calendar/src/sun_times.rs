@@ -0,0 +1,87 @@
+use crate::{
+    calendar::Calendar,
+    sun::{Location, SunTimes},
+    time_zone::{DstRule, TimeZone},
+};
+
+const PRAGUE: Location = Location::new(50.08, 14.42);
+const NEW_YORK: Location = Location::new(40.71, -74.01);
+const TROMSO: Location = Location::new(69.65, 18.96);
+
+/// The approximation is within a few minutes.
+const TOLERANCE: u16 = 3;
+
+fn sun_times(location: Location, time_zone: TimeZone, day: u8, month: u8, year: u16) -> SunTimes {
+    let mut calendar = Calendar::new(12, 0, 0, day, month, year);
+    calendar.set_time_zone(time_zone);
+    location.sun_times(&calendar)
+}
+
+fn time(hours: u16, minutes: u16) -> u16 {
+    hours * 60 + minutes
+}
+
+fn assert_close(actual: Option<u16>, expected: u16) {
+    let actual = actual.unwrap();
+    assert!(
+        actual.abs_diff(expected) <= TOLERANCE,
+        "{}:{:02} is not close to {}:{:02}",
+        actual / 60,
+        actual % 60,
+        expected / 60,
+        expected % 60
+    );
+}
+
+#[test]
+fn summer_solstice() {
+    let cet = TimeZone::new(60, DstRule::EuropeanUnion);
+    let sun = sun_times(PRAGUE, cet, 21, 6, 2023);
+
+    assert_close(sun.dawn(), time(4, 8));
+    assert_close(sun.sunrise(), time(4, 52));
+    assert_close(Some(sun.noon()), time(13, 3));
+    assert_close(sun.sunset(), time(21, 14));
+    assert_close(sun.dusk(), time(21, 58));
+}
+
+#[test]
+fn winter_solstice() {
+    let cet = TimeZone::new(60, DstRule::EuropeanUnion);
+    let sun = sun_times(PRAGUE, cet, 21, 12, 2023);
+
+    assert_close(sun.dawn(), time(7, 20));
+    assert_close(sun.sunrise(), time(7, 59));
+    assert_close(Some(sun.noon()), time(12, 0));
+    assert_close(sun.sunset(), time(16, 1));
+    assert_close(sun.dusk(), time(16, 40));
+}
+
+#[test]
+fn western_hemisphere() {
+    let eastern = TimeZone::new(-5 * 60, DstRule::UnitedStates);
+    let sun = sun_times(NEW_YORK, eastern, 1, 4, 2024);
+
+    assert_close(sun.dawn(), time(6, 13));
+    assert_close(sun.sunrise(), time(6, 40));
+    assert_close(sun.sunset(), time(19, 22));
+    assert_close(sun.dusk(), time(19, 49));
+}
+
+#[test]
+fn polar_day_and_night() {
+    let cet = TimeZone::new(60, DstRule::EuropeanUnion);
+
+    let polar_day = sun_times(TROMSO, cet, 21, 6, 2023);
+    assert_eq!(polar_day.dawn(), None);
+    assert_eq!(polar_day.sunrise(), None);
+    assert_eq!(polar_day.sunset(), None);
+    assert_eq!(polar_day.dusk(), None);
+
+    let polar_night = sun_times(TROMSO, cet, 21, 12, 2023);
+    assert_eq!(polar_night.sunrise(), None);
+    assert_eq!(polar_night.sunset(), None);
+    // the sun gets close enough to the horizon for a civil twilight
+    assert!(polar_night.dawn().is_some());
+    assert!(polar_night.dusk().is_some());
+}
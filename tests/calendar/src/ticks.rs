@@ -0,0 +1,145 @@
+use chrono::{Datelike, NaiveDate, TimeDelta};
+
+use crate::calendar::Calendar;
+
+const BASE_YEARS: [u16; 7] = [1, 1900, 1970, 2000, 2023, 2100, 9000];
+
+fn assert_matches_reference(base_year: u16, ticks: u32) {
+    let calendar = Calendar::from_ticks(base_year, ticks);
+    let reference = NaiveDate::from_ymd_opt(base_year as i32, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        + TimeDelta::seconds(ticks as i64);
+
+    let message = format!("base year {base_year}, ticks {ticks}");
+    crate::assert_matches_reference(&calendar, reference, &message);
+    assert_eq!(calendar.to_ticks(), ticks, "{message}");
+    assert_eq!(calendar.estimated_ticks(), ticks, "{message}");
+}
+
+#[test]
+fn full_range() {
+    for base_year in BASE_YEARS {
+        crate::full_range().for_each(|ticks| assert_matches_reference(base_year, ticks));
+    }
+}
+
+#[test]
+fn every_day() {
+    for base_year in BASE_YEARS {
+        crate::every_day().for_each(|ticks| assert_matches_reference(base_year, ticks));
+    }
+}
+
+#[test]
+fn every_date_to_ticks() {
+    for base_year in BASE_YEARS {
+        let base = NaiveDate::from_ymd_opt(base_year as i32, 1, 1).unwrap();
+        let mut date = base;
+        while (date - base).num_seconds() <= u32::MAX as i64 {
+            let calendar = Calendar::with_base_year(
+                base_year,
+                0,
+                0,
+                0,
+                date.day() as u8,
+                date.month() as u8,
+                date.year() as u16,
+            );
+
+            assert_eq!(
+                calendar.to_ticks() as i64,
+                (date - base).num_seconds(),
+                "{date}"
+            );
+            date = date.succ_opt().unwrap();
+        }
+    }
+}
+
+#[test]
+fn second_elapsed_matches_from_ticks() {
+    let mut calendar = Calendar::from_ticks(2023, 0);
+    for ticks in 1..=5 * 366 * 24 * 60 * 60 {
+        calendar.second_elapsed();
+        if ticks % 3607 == 0 {
+            assert!(calendar == Calendar::from_ticks(2023, ticks), "{ticks}");
+        }
+    }
+}
+
+// Calendars are equal by the instant, the local fields
+// and the ticks have to be compared on their own.
+#[test]
+fn second_elapsed_matches_fields_of_from_ticks() {
+    let mut calendar = Calendar::from_ticks(2023, 0);
+    for ticks in 1..=5 * 366 * 24 * 60 * 60 {
+        calendar.second_elapsed();
+        if ticks % 3607 == 0 {
+            let expected = Calendar::from_ticks(2023, ticks);
+            assert_eq!(
+                (calendar.day(), calendar.month(), calendar.year()),
+                (expected.day(), expected.month(), expected.year()),
+                "{ticks}"
+            );
+            assert_eq!(
+                (calendar.hours(), calendar.minutes(), calendar.seconds()),
+                (expected.hours(), expected.minutes(), expected.seconds()),
+                "{ticks}"
+            );
+            assert_eq!(calendar.estimated_ticks(), ticks, "{ticks}");
+        }
+    }
+}
+
+#[test]
+fn century_years() {
+    // 2100 is not a leap year, 2000 is
+    let base = Calendar::new(0, 0, 0, 1, 1, 2000);
+    let end_of_february_2000 = Calendar::with_base_year(2000, 0, 0, 0, 29, 2, 2000);
+    assert_eq!(end_of_february_2000.to_ticks(), (31 + 28) * 24 * 60 * 60);
+    assert_eq!(base.to_ticks(), 0);
+
+    let march_2100 = Calendar::with_base_year(2099, 0, 0, 0, 1, 3, 2100);
+    assert_eq!(march_2100.to_ticks(), (365 + 31 + 28) * 24 * 60 * 60);
+
+    let from_ticks = Calendar::from_ticks(2099, (365 + 31 + 28) * 24 * 60 * 60);
+    assert_eq!((from_ticks.day(), from_ticks.month()), (1, 3));
+}
+
+#[test]
+fn last_year_saturated() {
+    // 65535 is not a leap year
+    let last_second = 365 * 24 * 60 * 60 - 1;
+    let calendar = Calendar::from_ticks(u16::MAX, last_second);
+    assert_eq!(
+        (calendar.day(), calendar.month(), calendar.year()),
+        (31, 12, u16::MAX)
+    );
+    assert_eq!(
+        (calendar.hours(), calendar.minutes(), calendar.seconds()),
+        (23, 59, 59)
+    );
+
+    for ticks in [last_second + 1, u32::MAX] {
+        let calendar = Calendar::from_ticks(u16::MAX, ticks);
+        assert_eq!(
+            (calendar.day(), calendar.month(), calendar.year()),
+            (31, 12, u16::MAX),
+            "{ticks}"
+        );
+        assert_eq!(
+            (calendar.hours(), calendar.minutes(), calendar.seconds()),
+            (23, 59, 59),
+            "{ticks}"
+        );
+    }
+
+    let mut calendar = Calendar::from_ticks(u16::MAX, 0);
+    calendar.add_days(i32::MAX);
+    assert_eq!(
+        (calendar.day(), calendar.month(), calendar.year()),
+        (31, 12, u16::MAX)
+    );
+}
@@ -0,0 +1,130 @@
+use crate::{
+    calendar::Calendar,
+    time_zone::{DstRule, TimeZone},
+};
+
+const CET: TimeZone = TimeZone::new(60, DstRule::EuropeanUnion);
+const EASTERN: TimeZone = TimeZone::new(-5 * 60, DstRule::UnitedStates);
+
+/// Unix timestamp of 1. 1. 2023 00:00:00 UTC
+const BASE_2023: u32 = 1672531200;
+
+fn local_calendar(timestamp: u32, time_zone: TimeZone) -> Calendar {
+    let mut calendar = Calendar::from_ticks(2023, timestamp - BASE_2023);
+    calendar.set_time_zone(time_zone);
+    calendar
+}
+
+fn time(calendar: &Calendar) -> (u8, u8, u8, u8, u8, u16) {
+    (
+        calendar.hours(),
+        calendar.minutes(),
+        calendar.seconds(),
+        calendar.day(),
+        calendar.month(),
+        calendar.year(),
+    )
+}
+
+#[test]
+fn european_union_offsets() {
+    // 31. 3. 2024 01:00:00 UTC
+    let start = 1711846800;
+    // 27. 10. 2024 01:00:00 UTC
+    let end = 1729990800;
+
+    assert_eq!(CET.offset(start - 1), 60 * 60);
+    assert_eq!(CET.offset(start), 2 * 60 * 60);
+    assert_eq!(CET.offset(end - 1), 2 * 60 * 60);
+    assert_eq!(CET.offset(end), 60 * 60);
+
+    assert_eq!(CET.next_transition(start - 1), Some(start));
+    assert_eq!(CET.next_transition(start), Some(end));
+    // 30. 3. 2025 01:00:00 UTC
+    assert_eq!(CET.next_transition(end), Some(1743296400));
+}
+
+#[test]
+fn united_states_offsets() {
+    // 10. 3. 2024 07:00:00 UTC
+    let start = 1710054000;
+    // 3. 11. 2024 06:00:00 UTC
+    let end = 1730613600;
+
+    assert_eq!(EASTERN.offset(start - 1), -5 * 60 * 60);
+    assert_eq!(EASTERN.offset(start), -4 * 60 * 60);
+    assert_eq!(EASTERN.offset(end - 1), -4 * 60 * 60);
+    assert_eq!(EASTERN.offset(end), -5 * 60 * 60);
+    assert_eq!(EASTERN.next_transition(start - 1), Some(start));
+    assert_eq!(EASTERN.next_transition(start), Some(end));
+}
+
+#[test]
+fn no_dst() {
+    let zone = TimeZone::new(5 * 60 + 30, DstRule::None);
+    assert_eq!(zone.offset(1711846800), (5 * 60 + 30) * 60);
+    assert_eq!(zone.next_transition(1711846800), None);
+}
+
+#[test]
+fn spring_forward() {
+    let mut calendar = local_calendar(1711846800 - 1, CET);
+    assert_eq!(time(&calendar), (1, 59, 59, 31, 3, 2024));
+
+    calendar.second_elapsed();
+    assert_eq!(time(&calendar), (3, 0, 0, 31, 3, 2024));
+    assert_eq!(calendar.utc_offset(), 2 * 60 * 60);
+    assert_eq!(calendar.to_ticks(), 1711846800 - BASE_2023);
+}
+
+#[test]
+fn fall_back() {
+    let mut calendar = local_calendar(1729990800 - 1, CET);
+    assert_eq!(time(&calendar), (2, 59, 59, 27, 10, 2024));
+
+    calendar.second_elapsed();
+    assert_eq!(time(&calendar), (2, 0, 0, 27, 10, 2024));
+    assert_eq!(calendar.utc_offset(), 60 * 60);
+}
+
+#[test]
+fn local_time_to_ticks() {
+    for timestamp in (BASE_2023..BASE_2023 + 3 * 366 * 24 * 60 * 60).step_by(7919) {
+        let calendar = local_calendar(timestamp, CET);
+        assert_eq!(calendar.to_ticks(), timestamp - BASE_2023, "{timestamp}");
+    }
+}
+
+#[test]
+fn ticks_kept_across_years() {
+    // 31. 12. 2023 22:59:59 UTC, 23:59:59 local
+    let mut calendar = local_calendar(1704063599, CET);
+    assert_eq!(time(&calendar), (23, 59, 59, 31, 12, 2023));
+
+    calendar.second_elapsed();
+    assert_eq!(time(&calendar), (0, 0, 0, 1, 1, 2024));
+    assert_eq!(calendar.estimated_ticks(), 1704063600 - BASE_2023);
+}
+
+#[test]
+fn base_year_before_unix_epoch() {
+    let mut calendar = Calendar::new(0, 0, 0, 1, 6, 1960);
+    calendar.second_elapsed();
+    assert_eq!(time(&calendar), (0, 0, 1, 1, 6, 1960));
+
+    calendar.set_time_zone(CET);
+    assert_eq!(time(&calendar), (1, 0, 1, 1, 6, 1960));
+    assert_eq!(calendar.utc_offset(), 60 * 60);
+}
+
+#[test]
+fn base_year_after_unix_timestamps() {
+    let mut calendar = Calendar::new(0, 0, 0, 1, 1, 2200);
+    calendar.unfreeze();
+    assert_eq!(time(&calendar), (0, 0, 0, 1, 1, 2200));
+    assert_eq!(calendar.to_ticks(), 0);
+
+    calendar.set_time_zone(EASTERN);
+    assert_eq!(time(&calendar), (19, 0, 0, 31, 12, 2199));
+    assert_eq!(calendar.estimated_ticks(), 0);
+}
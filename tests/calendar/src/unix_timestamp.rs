@@ -0,0 +1,50 @@
+use chrono::DateTime;
+
+use crate::calendar::Calendar;
+
+fn assert_matches_reference(timestamp: u32) {
+    let calendar = Calendar::from_unix_timestamp(1970, timestamp);
+    let reference = DateTime::from_timestamp(timestamp as i64, 0)
+        .unwrap()
+        .naive_utc();
+
+    let message = format!("timestamp {timestamp}");
+    crate::assert_matches_reference(&calendar, reference, &message);
+    assert_eq!(calendar.to_unix_timestamp(), Some(timestamp), "{message}");
+}
+
+#[test]
+fn epoch() {
+    let calendar = Calendar::from_unix_timestamp(1970, 0);
+    assert_eq!(
+        (calendar.day(), calendar.month(), calendar.year()),
+        (1, 1, 1970)
+    );
+    assert_eq!(Calendar::new(0, 0, 0, 1, 1, 1970).to_unix_timestamp(), Some(0));
+}
+
+#[test]
+fn full_range() {
+    crate::full_range().for_each(assert_matches_reference);
+}
+
+#[test]
+fn every_day() {
+    crate::every_day().for_each(assert_matches_reference);
+}
+
+#[test]
+fn unrepresentable() {
+    assert_eq!(
+        Calendar::new(23, 59, 59, 31, 12, 1969).to_unix_timestamp(),
+        None
+    );
+    assert_eq!(
+        Calendar::new(6, 28, 15, 7, 2, 2106).to_unix_timestamp(),
+        Some(u32::MAX)
+    );
+    assert_eq!(
+        Calendar::new(6, 28, 16, 7, 2, 2106).to_unix_timestamp(),
+        None
+    );
+}
@@ -0,0 +1,150 @@
+use crate::calendar::{Calendar, CalendarError};
+
+fn fields(calendar: &Calendar) -> (u8, u8, u8, u8, u8, u16) {
+    (
+        calendar.hours(),
+        calendar.minutes(),
+        calendar.seconds(),
+        calendar.day(),
+        calendar.month(),
+        calendar.year(),
+    )
+}
+
+#[test]
+fn try_new() {
+    let calendar = Calendar::try_new(23, 59, 59, 29, 2, 2024).ok().unwrap();
+    assert_eq!(fields(&calendar), (23, 59, 59, 29, 2, 2024));
+
+    let invalid = [
+        ((24, 0, 0, 1, 1, 2023), CalendarError::InvalidTime),
+        ((0, 60, 0, 1, 1, 2023), CalendarError::InvalidTime),
+        ((0, 0, 60, 1, 1, 2023), CalendarError::InvalidTime),
+        ((0, 0, 0, 0, 1, 2023), CalendarError::InvalidDay),
+        ((0, 0, 0, 29, 2, 2023), CalendarError::InvalidDay),
+        ((0, 0, 0, 31, 4, 2023), CalendarError::InvalidDay),
+        ((0, 0, 0, 1, 0, 2023), CalendarError::InvalidMonth),
+        ((0, 0, 0, 1, 13, 2023), CalendarError::InvalidMonth),
+        ((0, 0, 0, 1, 1, 0), CalendarError::InvalidYear),
+    ];
+
+    for ((hours, minutes, seconds, day, month, year), error) in invalid {
+        assert_eq!(
+            Calendar::try_new(hours, minutes, seconds, day, month, year).err(),
+            Some(error)
+        );
+    }
+}
+
+#[test]
+fn try_with_base_year() {
+    assert_eq!(
+        Calendar::try_with_base_year(2023, 0, 0, 0, 31, 12, 2022).err(),
+        Some(CalendarError::InvalidYear)
+    );
+    assert_eq!(
+        Calendar::try_with_base_year(0, 0, 0, 0, 1, 1, 2023).err(),
+        Some(CalendarError::InvalidYear)
+    );
+
+    // the last second representable by the ticks
+    let last = Calendar::try_with_base_year(2000, 6, 28, 15, 7, 2, 2136)
+        .ok()
+        .unwrap();
+    assert_eq!(last.to_ticks(), u32::MAX);
+    assert_eq!(
+        Calendar::try_with_base_year(2000, 6, 28, 16, 7, 2, 2136).err(),
+        Some(CalendarError::TicksOverflow)
+    );
+    assert_eq!(
+        Calendar::try_with_base_year(1, 0, 0, 0, 1, 1, 9999).err(),
+        Some(CalendarError::TicksOverflow)
+    );
+}
+
+#[test]
+fn invalid_values_clamped() {
+    let calendar = Calendar::with_base_year(2023, 99, 99, 99, 31, 2, 2022);
+    assert_eq!(fields(&calendar), (23, 59, 59, 28, 2, 2023));
+
+    let calendar = Calendar::with_base_year(2023, 0, 0, 0, 0, 0, 2024);
+    assert_eq!(fields(&calendar), (0, 0, 0, 1, 1, 2024));
+
+    let calendar = Calendar::with_base_year(2023, 0, 0, 0, 99, 99, 2024);
+    assert_eq!(fields(&calendar), (0, 0, 0, 31, 12, 2024));
+
+    let calendar = Calendar::new(0, 0, 0, 1, 1, 0);
+    assert_eq!(fields(&calendar), (0, 0, 0, 1, 1, 1));
+    assert_eq!(calendar.to_ticks(), 0);
+
+    // ticks too far after the base year saturate
+    let calendar = Calendar::with_base_year(1, 0, 0, 0, 1, 1, 9999);
+    assert_eq!(calendar.to_ticks(), u32::MAX);
+
+    assert_eq!(fields(&Calendar::from_ticks(0, 0)), (0, 0, 0, 1, 1, 1));
+}
+
+#[test]
+fn days_in_month() {
+    assert_eq!(Calendar::days_in_month(2, true), Ok(29));
+    assert_eq!(Calendar::days_in_month(2, false), Ok(28));
+    assert_eq!(Calendar::days_in_month(12, false), Ok(31));
+    assert_eq!(
+        Calendar::days_in_month(0, false),
+        Err(CalendarError::InvalidMonth)
+    );
+    assert_eq!(
+        Calendar::days_in_month(13, true),
+        Err(CalendarError::InvalidMonth)
+    );
+}
+
+#[test]
+fn try_setters() {
+    let mut calendar = Calendar::with_base_year(2000, 12, 30, 15, 29, 2, 2024);
+
+    assert_eq!(calendar.try_set_hours(24), Err(CalendarError::InvalidTime));
+    assert_eq!(
+        calendar.try_set_minutes(60),
+        Err(CalendarError::InvalidTime)
+    );
+    assert_eq!(
+        calendar.try_set_seconds(60),
+        Err(CalendarError::InvalidTime)
+    );
+    assert_eq!(calendar.try_set_day(30), Err(CalendarError::InvalidDay));
+    assert_eq!(calendar.try_set_day(0), Err(CalendarError::InvalidDay));
+    assert_eq!(calendar.try_set_month(13), Err(CalendarError::InvalidMonth));
+    // 29. 4. exists, 29. 2. 2023 does not
+    assert_eq!(calendar.try_set_year(2023), Err(CalendarError::InvalidDay));
+    assert_eq!(calendar.try_set_year(1999), Err(CalendarError::InvalidYear));
+    assert_eq!(fields(&calendar), (12, 30, 15, 29, 2, 2024));
+
+    assert_eq!(calendar.try_set_hours(23), Ok(()));
+    assert_eq!(calendar.try_set_minutes(0), Ok(()));
+    assert_eq!(calendar.try_set_seconds(59), Ok(()));
+    assert_eq!(calendar.try_set_month(4), Ok(()));
+    assert_eq!(calendar.try_set_day(30), Ok(()));
+    assert_eq!(calendar.try_set_year(2023), Ok(()));
+    assert_eq!(fields(&calendar), (23, 0, 59, 30, 4, 2023));
+
+    assert_eq!(calendar.try_set_month(2), Err(CalendarError::InvalidDay));
+}
+
+#[test]
+fn setters_clamp_day() {
+    let mut calendar = Calendar::with_base_year(2000, 0, 0, 0, 31, 1, 2024);
+    calendar.set_month(2);
+    assert_eq!((calendar.day(), calendar.month()), (29, 2));
+    calendar.set_year(2023);
+    assert_eq!((calendar.day(), calendar.month()), (28, 2));
+    calendar.set_month(0);
+    assert_eq!((calendar.day(), calendar.month()), (28, 1));
+}
+
+#[test]
+fn second_elapsed_at_the_end_of_ticks() {
+    let mut calendar = Calendar::from_ticks(2000, u32::MAX);
+    calendar.second_elapsed();
+    assert_eq!(calendar.estimated_ticks(), u32::MAX);
+}